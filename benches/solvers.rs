@@ -0,0 +1,230 @@
+//! Times every registered solver and variant on the local inputs.
+//!
+//! `cargo bench --bench solvers -- [DAY] [--inputs DIR] [--json PATH]` prints mean, median
+//! and standard deviation per solver, compares each variant with the default for its part, and
+//! writes the same numbers as JSON (to `target/bench/solvers.json` unless told otherwise).
+//! With `--synthetic SEED` the inputs are generated instead of read, see `aocrs_2024::generate`.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use aocrs_2024::{
+    generate,
+    runner::{self, Solver},
+};
+use clap::Parser;
+use serde_json::{json, Value};
+
+#[derive(Parser)]
+struct Args {
+    /// Only benchmark this day.
+    day: Option<u8>,
+    /// Year to benchmark. Defaults to the latest year.
+    #[arg(long)]
+    year: Option<u32>,
+    /// Directory holding dayN.txt inputs, for example synthetic ones. Defaults to
+    /// input/<YEAR>.
+    #[arg(long)]
+    inputs: Option<PathBuf>,
+    /// Generate inputs from this seed instead of reading them.
+    #[arg(long, conflicts_with = "inputs")]
+    synthetic: Option<u64>,
+    /// Where to write the JSON report.
+    #[arg(long, default_value = "target/bench/solvers.json")]
+    json: PathBuf,
+    /// Most samples to take per solver.
+    #[arg(long, default_value_t = 100)]
+    samples: usize,
+    /// Stop sampling a solver after this many seconds, once it has a few samples.
+    #[arg(long, default_value_t = 3.0)]
+    seconds: f64,
+    /// Passed by `cargo bench`.
+    #[arg(long, hide = true)]
+    bench: bool,
+}
+
+const MIN_SAMPLES: usize = 5;
+
+struct Stats {
+    mean: f64,
+    median: f64,
+    stddev: f64,
+}
+
+impl Stats {
+    fn of(samples: &[Duration]) -> Self {
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_by(f64::total_cmp);
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+        let middle = nanos.len() / 2;
+        let median = if nanos.len().is_multiple_of(2) {
+            (nanos[middle - 1] + nanos[middle]) / 2.0
+        } else {
+            nanos[middle]
+        };
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        Stats {
+            mean,
+            median,
+            stddev: variance.sqrt(),
+        }
+    }
+
+    fn json(&self) -> Value {
+        json!({
+            "mean_ns": self.mean.round(),
+            "median_ns": self.median.round(),
+            "stddev_ns": self.stddev.round(),
+        })
+    }
+}
+
+fn show(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+struct Measurement {
+    answer: String,
+    parse: Stats,
+    solve: Stats,
+    samples: usize,
+}
+
+/// A day's input with the parameter overrides it needs, and where it came from.
+struct Input {
+    text: String,
+    overrides: Vec<(String, String)>,
+    source: String,
+}
+
+fn load(year: u32, day: u8, inputs: &Path, args: &Args) -> Option<Input> {
+    match args.synthetic {
+        Some(seed) => {
+            let generated = generate::generator(year, day)?.run(seed, &[]).ok()?;
+            Some(Input {
+                text: generated.input,
+                overrides: generated.params,
+                source: format!("synthetic, seed {seed}"),
+            })
+        }
+        None => {
+            let path = inputs.join(format!("day{day}.txt"));
+            Some(Input {
+                text: std::fs::read_to_string(&path).ok()?,
+                overrides: Vec::new(),
+                source: path.display().to_string(),
+            })
+        }
+    }
+}
+
+fn measure(solver: &Solver, input: &Input, args: &Args) -> Result<Measurement, String> {
+    let run = || {
+        solver
+            .run_with(&input.text, &input.overrides)
+            .map_err(|e| e.to_string())
+    };
+    // The first run warms caches and tells us whether the input parses at all.
+    let answer = run()?.answer;
+    let budget = Duration::from_secs_f64(args.seconds);
+    let start = Instant::now();
+    let (mut parse, mut solve) = (Vec::new(), Vec::new());
+    while solve.len() < args.samples.max(1)
+        && (solve.len() < MIN_SAMPLES || start.elapsed() < budget)
+    {
+        let outcome = run()?;
+        parse.push(outcome.parse);
+        solve.push(outcome.solve);
+    }
+    Ok(Measurement {
+        answer,
+        parse: Stats::of(&parse),
+        solve: Stats::of(&solve),
+        samples: solve.len(),
+    })
+}
+
+fn main() {
+    let args = Args::parse();
+    let year = args.year.unwrap_or_else(runner::latest_year);
+    let dir = args
+        .inputs
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(format!("input/{year}")));
+    let mut inputs = HashMap::new();
+    let mut defaults = HashMap::new();
+    let mut results = Vec::new();
+    for solver in runner::solvers() {
+        if solver.year != year || args.day.is_some_and(|day| day != solver.day) {
+            continue;
+        }
+        let Some(input) = inputs
+            .entry(solver.day)
+            .or_insert_with(|| load(year, solver.day, &dir, &args))
+        else {
+            continue;
+        };
+        let measurement = match measure(solver, input, &args) {
+            Ok(measurement) => measurement,
+            Err(e) => {
+                eprintln!("{solver}: failed\n{e}");
+                continue;
+            }
+        };
+        let Measurement { solve, parse, .. } = &measurement;
+        let comparison = match solver.variant {
+            None => {
+                defaults.insert((solver.day, solver.part), solve.median);
+                String::new()
+            }
+            Some(_) => defaults
+                .get(&(solver.day, solver.part))
+                .map(|default| format!("  {:.2}x default", solve.median / default))
+                .unwrap_or_default(),
+        };
+        println!(
+            "{:<28} median {:>10}  mean {:>10}  stddev {:>10}  parse {:>10}  ({} runs){comparison}",
+            solver.to_string(),
+            show(solve.median),
+            show(solve.mean),
+            show(solve.stddev),
+            show(parse.median),
+            measurement.samples,
+        );
+        results.push(json!({
+            "day": solver.day,
+            "part": solver.part,
+            "variant": solver.variant,
+            "input": input.source,
+            "answer": measurement.answer,
+            "samples": measurement.samples,
+            "parse": parse.json(),
+            "solve": solve.json(),
+        }));
+    }
+    let mut missing: Vec<_> = inputs
+        .iter()
+        .filter(|(_, i)| i.is_none())
+        .map(|(d, _)| d)
+        .collect();
+    missing.sort();
+    for day in missing {
+        eprintln!("day {day}: skipped, no input in {}", dir.display());
+    }
+
+    let report = json!({
+        "year": year,
+        "timestamp": SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
+        "results": results,
+    });
+    if let Some(dir) = args.json.parent() {
+        std::fs::create_dir_all(dir).expect("cannot create the report directory");
+    }
+    std::fs::write(&args.json, serde_json::to_string_pretty(&report).unwrap())
+        .expect("cannot write the JSON report");
+    println!("wrote {}", args.json.display());
+}
@@ -0,0 +1,10 @@
+#![no_main]
+
+use aocrs_2024::runner;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    for solver in runner::solvers().iter().filter(|s| (s.year, s.day) == (2024, 13)) {
+        let _ = solver.run(input);
+    }
+});
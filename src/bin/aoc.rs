@@ -0,0 +1,416 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, ErrorKind, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use aocrs_2024::{
+    explain, params,
+    report::{Answers, Environment, Report},
+    runner::{self, Solver},
+    stream,
+    visualize::{self, Every, ImageFormat, Images, Terminal},
+};
+use clap::{Parser, ValueEnum};
+use itertools::Itertools;
+use serde_json::json;
+
+/// Runs Advent of Code solutions and reports their answers and timings.
+#[derive(Parser)]
+struct Args {
+    /// Day to run; every day is run when omitted.
+    day: Option<u8>,
+    /// Year the day belongs to. Defaults to the latest year.
+    #[arg(short, long)]
+    year: Option<u32>,
+    /// Only run this part.
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Run this variant instead of the default solver, e.g. HASH or STRING_CONCAT.
+    #[arg(short, long, conflicts_with = "all_variants")]
+    variant: Option<String>,
+    /// Run the default solver and every variant.
+    #[arg(short, long)]
+    all_variants: bool,
+    /// Input file, or `-` for stdin. Defaults to input/<YEAR>/day<DAY>.txt.
+    #[arg(short, long, requires = "day")]
+    input: Option<PathBuf>,
+    /// Override a puzzle parameter, e.g. `-P width=11`. May be repeated.
+    #[arg(short = 'P', long = "param", value_name = "KEY=VALUE", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+    /// TOML file with `[dayN]` tables of parameter overrides; `--param` wins over it.
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+    /// Show how the answers were reached (days 1 to 5, 7, 13, 16, 21 and 22).
+    #[arg(long, value_enum, value_name = "FORMAT", requires = "day", conflicts_with_all = ["variant", "all_variants"])]
+    explain: Option<Format>,
+    /// Watch the day's simulation instead of solving it (days 6, 14, 15, 16 and 18).
+    #[arg(
+        long,
+        value_enum,
+        value_name = "OUTPUT",
+        requires = "day",
+        conflicts_with = "explain"
+    )]
+    visualize: Option<Output>,
+    /// Read the input a line at a time instead of all at once, for inputs larger than memory
+    /// (days 1, 2, 7, 13, 14 part 1, 22 and 23). Works with `--input -` too.
+    #[arg(long, requires = "day", conflicts_with_all = ["variant", "all_variants", "explain", "visualize", "report"])]
+    stream: bool,
+    /// Directory for image frames. Defaults to target/frames/<YEAR>/day<DAY>.
+    #[arg(long)]
+    frames: Option<PathBuf>,
+    /// Only show every Nth step of the simulation; the last one is always shown.
+    #[arg(long, default_value_t = 1)]
+    every: usize,
+    /// Pause between terminal frames, in milliseconds.
+    #[arg(long, default_value_t = 50)]
+    delay: u64,
+    /// Pixels per grid square in image frames.
+    #[arg(long, default_value_t = 4)]
+    scale: usize,
+    /// Print a report of the whole run instead, with input hashes and checked answers.
+    #[arg(long, value_enum, value_name = "FORMAT", conflicts_with_all = ["explain", "visualize"])]
+    report: Option<ReportFormat>,
+    /// Known answers for the report to check against.
+    #[arg(long, default_value = "answers.toml", requires = "report")]
+    answers: PathBuf,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// Indented `key: value` lines.
+    Text,
+    /// One JSON object per part, in an array.
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    Json,
+    Markdown,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Output {
+    /// Redraw the terminal for every frame.
+    Ansi,
+    /// Numbered PPM images.
+    Ppm,
+    /// Numbered PNG images.
+    #[cfg(feature = "png")]
+    Png,
+}
+
+impl Args {
+    fn year(&self) -> u32 {
+        self.year.unwrap_or_else(runner::latest_year)
+    }
+
+    fn selects(&self, solver: &Solver) -> bool {
+        solver.year == self.year()
+            && self.day.is_none_or(|day| solver.day == day)
+            && self.part.is_none_or(|part| solver.part == part)
+            && match (&self.variant, solver.variant) {
+                (Some(wanted), Some(variant)) => variant.eq_ignore_ascii_case(wanted),
+                (Some(_), None) => false,
+                (None, variant) => self.all_variants || variant.is_none(),
+            }
+    }
+}
+
+fn parse_param(arg: &str) -> Result<(String, String), String> {
+    let (name, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("`{arg}` should look like KEY=VALUE"))?;
+    Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
+fn open_input(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    if path == Path::new("-") {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+fn read_input(path: &Path) -> io::Result<String> {
+    let mut input = String::new();
+    open_input(path)?.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// The path of the input for `day`, from `--input` or the usual place.
+fn input_path(args: &Args, day: u8) -> PathBuf {
+    args.input
+        .clone()
+        .unwrap_or_else(|| runner::input_path(args.year(), day))
+}
+
+/// The input for `day`, from `--input` or the usual place. Reports the error if there is none.
+fn day_input(args: &Args, day: u8) -> Option<String> {
+    let path = input_path(args, day);
+    read_input(&path)
+        .inspect_err(|e| eprintln!("day {day}: cannot read {}: {e}", path.display()))
+        .ok()
+}
+
+/// The answers at `path`; none if there is no such file.
+fn read_answers(path: &Path) -> Result<Answers, String> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Answers::parse(&text),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn explain(args: &Args, day: u8, format: Format, overrides: &[(String, String)]) -> ExitCode {
+    let Some(explainer) = explain::explainer(args.year(), day) else {
+        eprintln!("day {day} cannot explain its answers");
+        return ExitCode::FAILURE;
+    };
+    let Some(input) = day_input(args, day) else {
+        return ExitCode::FAILURE;
+    };
+    let mut reports = Vec::new();
+    for part in args.part.map_or(1..=2, |part| part..=part) {
+        match explainer.run(&input, overrides, part) {
+            Ok(explanation) => reports.push((part, explanation)),
+            Err(e) => {
+                eprintln!("day {day} part {part} failed:\n{e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    match format {
+        Format::Text => {
+            for (part, explanation) in reports {
+                println!("day {day} part {part}: {}", explanation.answer);
+                for line in explain::render_text(&explanation.witness).lines() {
+                    println!("  {line}");
+                }
+            }
+        }
+        Format::Json => {
+            let reports = reports
+                .into_iter()
+                .map(|(part, explanation)| {
+                    json!({
+                        "year": args.year(),
+                        "day": day,
+                        "part": part,
+                        "answer": explanation.answer,
+                        "witness": explanation.witness,
+                    })
+                })
+                .collect_vec();
+            println!("{}", serde_json::to_string_pretty(&reports).unwrap());
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn stream(args: &Args, day: u8, overrides: &[(String, String)]) -> ExitCode {
+    let Some(streamer) = stream::streamer(args.year(), day) else {
+        eprintln!("day {day} cannot stream its input");
+        return ExitCode::FAILURE;
+    };
+    if let Some(part) = args.part.filter(|part| !streamer.parts.contains(part)) {
+        eprintln!("day {day} part {part} needs the whole input at once");
+        return ExitCode::FAILURE;
+    }
+    let path = input_path(args, day);
+    let start = Instant::now();
+    let answers = match open_input(&path) {
+        Ok(mut reader) => streamer.run(&mut reader, overrides),
+        Err(e) => {
+            eprintln!("day {day}: cannot read {}: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    match answers {
+        Ok(answers) => {
+            for (part, answer) in answers {
+                if args.part.is_none_or(|wanted| wanted == part) {
+                    println!("day {day} part {part}: {answer}");
+                }
+            }
+            println!("    streamed in {:?}", start.elapsed());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("day {day} failed:\n{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn visualize(args: &Args, day: u8, output: Output, overrides: &[(String, String)]) -> ExitCode {
+    let Some(animation) = visualize::animation(args.year(), day) else {
+        eprintln!("day {day} has no visualization");
+        return ExitCode::FAILURE;
+    };
+    let Some(input) = day_input(args, day) else {
+        return ExitCode::FAILURE;
+    };
+    let dir = args
+        .frames
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(format!("target/frames/{}/day{day}", args.year())));
+    let format = match output {
+        Output::Ansi => None,
+        Output::Ppm => Some(ImageFormat::Ppm),
+        #[cfg(feature = "png")]
+        Output::Png => Some(ImageFormat::Png),
+    };
+    let result = match format {
+        None => {
+            let terminal = Terminal {
+                out: io::stdout().lock(),
+                delay: Duration::from_millis(args.delay),
+            };
+            animation.run(&input, overrides, &mut Every::new(args.every, terminal))
+        }
+        Some(format) => Images::new(&dir, format, args.scale)
+            .map_err(visualize::VisualizeError::Io)
+            .and_then(|images| {
+                let mut sink = Every::new(args.every, images);
+                animation.run(&input, overrides, &mut sink)?;
+                println!("wrote {} frames to {}", sink.inner.written(), dir.display());
+                Ok(())
+            }),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("day {day} visualization failed:\n{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let solvers = runner::solvers()
+        .iter()
+        .filter(|s| args.selects(s))
+        .collect_vec();
+    if solvers.is_empty() {
+        eprintln!("no solver matches the given year, day, part and variant");
+        return ExitCode::FAILURE;
+    }
+
+    let mut overrides = match &args.config {
+        Some(path) => match std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| params::parse_config(&text))
+        {
+            Ok(overrides) => overrides,
+            Err(e) => {
+                eprintln!("cannot read config {}: {e}", path.display());
+                return ExitCode::FAILURE;
+            }
+        },
+        None => params::Overrides::new(),
+    };
+    if let Some(day) = args.day {
+        overrides
+            .entry(day)
+            .or_default()
+            .extend(args.params.iter().cloned());
+    } else if !args.params.is_empty() {
+        eprintln!("--param needs a day, parameters differ from day to day");
+        return ExitCode::FAILURE;
+    }
+
+    if let Some(day) = args.day {
+        let overrides = overrides.get(&day).map_or(&[][..], Vec::as_slice);
+        if let Some(format) = args.explain {
+            return explain(&args, day, format, overrides);
+        }
+        if let Some(output) = args.visualize {
+            return visualize(&args, day, output, overrides);
+        }
+        if args.stream {
+            return stream(&args, day, overrides);
+        }
+    }
+
+    let answers = match args.report {
+        Some(_) => match read_answers(&args.answers) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("cannot read answers {}: {e}", args.answers.display());
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Answers::default(),
+    };
+    let mut report = args.report.map(|_| Report::new(Environment::current()));
+
+    let start = Instant::now();
+    let mut failed = false;
+    for (day, solvers) in &solvers.into_iter().chunk_by(|s| s.day) {
+        let path = input_path(&args, day);
+        let input = match read_input(&path) {
+            Ok(input) => input,
+            Err(e) if e.kind() == ErrorKind::NotFound && args.day.is_none() => {
+                eprintln!("day {day}: skipped, no input at {}", path.display());
+                continue;
+            }
+            Err(e) => {
+                eprintln!("day {day}: cannot read {}: {e}", path.display());
+                failed = true;
+                continue;
+            }
+        };
+        let overrides = overrides.get(&day).map_or(&[][..], Vec::as_slice);
+        for solver in solvers {
+            let result = solver.run_with(&input, overrides);
+            match &result {
+                Ok(outcome) if report.is_none() => {
+                    println!(
+                        "{solver}: {}\n    parse {:?}, solve {:?}",
+                        outcome.answer, outcome.parse, outcome.solve
+                    );
+                    if !outcome.metrics.is_empty() {
+                        println!("    work: {}", outcome.metrics);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    eprintln!("{solver} failed:\n{e}");
+                    failed = true;
+                }
+            }
+            if let Some(report) = &mut report {
+                report.record(solver, &input, result, &answers);
+            }
+        }
+    }
+    match (&mut report, args.report) {
+        (Some(report), Some(format)) => {
+            report.total = start.elapsed();
+            match format {
+                ReportFormat::Json => {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&report.to_json()).unwrap()
+                    )
+                }
+                ReportFormat::Markdown => print!("{}", report.to_markdown()),
+            }
+            failed |= !report.passed();
+        }
+        _ if args.day.is_none() => println!("total {:?}", start.elapsed()),
+        _ => {}
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,115 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use aocrs_2024::{
+    generate::{self, Generator},
+    runner,
+};
+use clap::Parser;
+
+/// Writes made-up puzzle inputs, for benchmarks and stress tests.
+///
+/// Each input goes to DIR/day<N>.txt. Inputs that need different puzzle parameters, such as
+/// another room size on day 14, get them in DIR/params.toml, ready for `aoc --config`.
+#[derive(Parser)]
+struct Args {
+    /// Day to generate; every day of the year is generated when omitted.
+    day: Option<u8>,
+    /// Year the day belongs to. Defaults to the latest year.
+    #[arg(short, long)]
+    year: Option<u32>,
+    /// Seed for the random choices; the same seed and knobs give the same input.
+    #[arg(short, long, default_value_t = 2024)]
+    seed: u64,
+    /// Directory to write to, or `-` to print a single day's input instead. Defaults to
+    /// target/synthetic/<YEAR>.
+    #[arg(short, long)]
+    out: Option<PathBuf>,
+    /// Set a knob of the generator, e.g. `-K size=500`. May be repeated; needs a day.
+    #[arg(short = 'K', long = "knob", value_name = "KEY=VALUE", value_parser = parse_knob, requires = "day")]
+    knobs: Vec<(String, String)>,
+    /// List each day's knobs and exit.
+    #[arg(long)]
+    list: bool,
+}
+
+fn parse_knob(arg: &str) -> Result<(String, String), String> {
+    let (name, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("`{arg}` should look like KEY=VALUE"))?;
+    Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let year = args.year.unwrap_or_else(runner::latest_year);
+    let out = args
+        .out
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(format!("target/synthetic/{year}")));
+    let generators: Vec<&Generator> = generate::generators()
+        .iter()
+        .filter(|g| g.year == year && args.day.is_none_or(|day| g.day == day))
+        .collect();
+    if generators.is_empty() {
+        match args.day {
+            Some(day) => eprintln!("there is no day {day} in {year}"),
+            None => eprintln!("there are no days in {year}"),
+        }
+        return ExitCode::FAILURE;
+    }
+    if args.list {
+        for generator in generators {
+            println!("day {}: {}", generator.day, generator.knobs.join(", "));
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let to_stdout = out.as_os_str() == "-";
+    if to_stdout && args.day.is_none() {
+        eprintln!("--out - needs a day, one input at a time");
+        return ExitCode::FAILURE;
+    }
+    if !to_stdout {
+        if let Err(e) = std::fs::create_dir_all(&out) {
+            eprintln!("cannot create {}: {e}", out.display());
+            return ExitCode::FAILURE;
+        }
+    }
+    let mut config = String::new();
+    for generator in generators {
+        let day = generator.day;
+        let generated = match generator.run(args.seed, &args.knobs) {
+            Ok(generated) => generated,
+            Err(e) => {
+                eprintln!("day {day}: {e}");
+                return ExitCode::FAILURE;
+            }
+        };
+        if to_stdout {
+            println!("{}", generated.input);
+            continue;
+        }
+        if !generated.params.is_empty() {
+            config.push_str(&format!("[day{day}]\n"));
+            for (name, value) in &generated.params {
+                config.push_str(&format!("{name} = {value}\n"));
+            }
+            config.push('\n');
+        }
+        let path = out.join(format!("day{day}.txt"));
+        if let Err(e) = std::fs::write(&path, generated.input + "\n") {
+            eprintln!("cannot write {}: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+        println!("day {day}: wrote {}", path.display());
+    }
+    if !config.is_empty() {
+        let path = out.join("params.toml");
+        if let Err(e) = std::fs::write(&path, config) {
+            eprintln!("cannot write {}: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+        println!("parameters for these inputs are in {}", path.display());
+    }
+    ExitCode::SUCCESS
+}
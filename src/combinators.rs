@@ -0,0 +1,216 @@
+//! nom parsers for the shapes that keep turning up in puzzle input.
+//!
+//! Every line-based combinator skips leading and trailing spaces on a line and accepts both
+//! `\n` and `\r\n` line endings, so indented examples and copy/pasted inputs parse the same.
+
+use std::str::FromStr;
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, digit1, line_ending, multispace0, one_of, space0},
+    combinator::{all_consuming, cut, eof, map_res, not, opt, recognize},
+    error::{context, VerboseError, VerboseErrorKind},
+    multi::many1,
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    Finish, IResult, Parser,
+};
+
+use crate::{
+    grid::Grid,
+    parse::{ParseError, ParseResult},
+};
+
+pub type Res<'a, O> = IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// Runs `parser` over the whole of `input`, allowing surrounding whitespace, and turns a
+/// failure into a [`ParseError`] pointing at where parsing stopped.
+pub fn finish<'a, O>(
+    input: &'a str,
+    parser: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> ParseResult<O> {
+    let trimmed = input.trim_end();
+    all_consuming(preceded(multispace0, parser))(trimmed)
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|error| to_parse_error(input, error))
+}
+
+fn to_parse_error(input: &str, error: VerboseError<&str>) -> ParseError {
+    let Some(&(rest, _)) = error.errors.first() else {
+        return ParseError::at_offset(input, 0, "invalid input");
+    };
+    let message = error
+        .errors
+        .iter()
+        .find_map(|(_, kind)| match kind {
+            VerboseErrorKind::Context(context) => Some(format!("expected {context}")),
+            _ => None,
+        })
+        .unwrap_or_else(|| match error.errors[0].1 {
+            VerboseErrorKind::Char(c) => format!("expected `{c}`"),
+            _ if rest.is_empty() => "unexpected end of input".to_string(),
+            _ => "unexpected input".to_string(),
+        });
+    let width = rest.chars().next().map_or(0, char::len_utf8);
+    ParseError::at(input, &rest[..width], message)
+}
+
+/// An optionally signed integer, `+` included.
+pub fn signed<T: FromStr>(input: &str) -> Res<'_, T> {
+    context(
+        "a number",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse),
+    )(input)
+}
+
+/// An integer without a sign.
+pub fn unsigned<T: FromStr>(input: &str) -> Res<'_, T> {
+    context("a number", map_res(digit1, str::parse))(input)
+}
+
+/// `p` surrounded by optional spaces or tabs.
+pub fn ws<'a, O>(
+    p: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Res<'a, O> {
+    delimited(space0, p, space0)
+}
+
+/// One or more `p`, separated by `separator`. Once a separator has been consumed the next
+/// item must follow, so errors point into the item rather than at the separator.
+pub fn separated<'a, O, S>(
+    mut separator: impl Parser<&'a str, S, VerboseError<&'a str>>,
+    mut p: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Res<'a, Vec<O>> {
+    move |input| {
+        let (mut input, first) = p.parse(input)?;
+        let mut items = vec![first];
+        while let Ok((rest, _)) = separator.parse(input) {
+            let (rest, item) = cut(|i| p.parse(i))(rest)?;
+            items.push(item);
+            input = rest;
+        }
+        Ok((input, items))
+    }
+}
+
+/// One or more `p`, separated by commas.
+pub fn comma_list<'a, O>(
+    p: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Res<'a, Vec<O>> {
+    separated(ws(char(',')), p)
+}
+
+/// `key: value`, for example `Register A: 729`.
+pub fn key_value<'a, O>(
+    key: &'static str,
+    value: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Res<'a, O> {
+    context(key, preceded(tuple((tag(key), ws(char(':')))), value))
+}
+
+/// A line break that is followed by another non-blank line.
+fn line_break(input: &str) -> Res<'_, &str> {
+    terminated(line_ending, not(pair(space0, alt((line_ending, eof)))))(input)
+}
+
+/// A line break followed by at least one blank line.
+fn blank_lines(input: &str) -> Res<'_, &str> {
+    recognize(pair(line_ending, many1(pair(space0, line_ending))))(input)
+}
+
+/// `p` on a line of its own, ignoring indentation and trailing spaces.
+pub fn line<'a, O>(
+    p: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Res<'a, O> {
+    ws(p)
+}
+
+/// `p` once per line, stopping at a blank line or the end of the input.
+pub fn lines<'a, O>(
+    p: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Res<'a, Vec<O>> {
+    separated(line_break, line(p))
+}
+
+/// Two lines, the second directly after the first.
+pub fn then_line<'a, A, B>(
+    first: impl Parser<&'a str, A, VerboseError<&'a str>>,
+    second: impl Parser<&'a str, B, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Res<'a, (A, B)> {
+    pair(terminated(line(first), line_break), cut(line(second)))
+}
+
+/// One or more `p`, separated by blank lines.
+pub fn sections<'a, O>(
+    p: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Res<'a, Vec<O>> {
+    separated(blank_lines, p)
+}
+
+/// Exactly two sections separated by blank lines.
+pub fn two_sections<'a, A, B>(
+    first: impl Parser<&'a str, A, VerboseError<&'a str>>,
+    second: impl Parser<&'a str, B, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> Res<'a, (A, B)> {
+    pair(terminated(first, blank_lines), cut(second))
+}
+
+/// A rectangular grid of the bytes in `allowed`, one row per line.
+pub fn char_grid<'a>(allowed: &'static [u8]) -> impl FnMut(&'a str) -> Res<'a, Grid<u8>> {
+    move |input| {
+        let (rest, rows) = lines(context(
+            "a row of the grid",
+            take_while1(|c: char| c.is_ascii() && allowed.contains(&(c as u8))),
+        ))(input)?;
+        let width = rows[0].len();
+        if let Some(row) = rows.iter().find(|r| r.len() != width) {
+            return Err(nom::Err::Failure(VerboseError {
+                errors: vec![(
+                    &row[row.len().min(width)..],
+                    VerboseErrorKind::Context("rows of equal width"),
+                )],
+            }));
+        }
+        Ok((rest, Grid::from_vec(rows.concat().into_bytes(), width)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_indented_crlf_lines() {
+        let input = "1, -2\r\n    +3,4\r\n";
+        let parsed = finish(input, lines(comma_list(signed::<i32>))).unwrap();
+        assert_eq!(parsed, vec![vec![1, -2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn splits_sections_on_blank_lines() {
+        let input = "a: 1\nb: 2\n   \n\n  a: 3\n  b: 4";
+        let section = then_line(
+            key_value("a", unsigned::<u8>),
+            key_value("b", unsigned::<u8>),
+        );
+        let parsed = finish(input, sections(section)).unwrap();
+        assert_eq!(parsed, vec![(1, 2), (3, 4)]);
+    }
+
+    #[test]
+    fn reports_where_parsing_stopped() {
+        let input = "1,2\n3,x\n";
+        let error = finish(input, lines(comma_list(signed::<i32>))).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.message, "expected a number");
+    }
+
+    #[test]
+    fn grids_must_be_rectangular() {
+        let grid = finish("#.\n.#", char_grid(b"#.")).unwrap();
+        assert_eq!((grid.width, grid.height), (2, 2));
+        let error = finish("#.\n.", char_grid(b"#.")).unwrap_err();
+        assert_eq!(error.line, 2);
+    }
+}
@@ -0,0 +1,177 @@
+//! Explanations: an answer together with the evidence for it, such as the operators that
+//! balance each equation or the path a search settled on.
+//!
+//! Days opt in by implementing [`Explain`]. Witnesses are JSON values so they can be printed as
+//! JSON for tools or as indented text for people, see [`render_text`].
+
+use std::{fmt::Write, sync::LazyLock};
+
+use serde_json::Value;
+
+use crate::{params::Params, runner::RunError, solution::Solution, year2024};
+
+/// An answer and the witness that backs it up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub answer: String,
+    pub witness: Value,
+}
+
+impl Explanation {
+    pub fn new(answer: impl ToString, witness: Value) -> Self {
+        Explanation {
+            answer: answer.to_string(),
+            witness,
+        }
+    }
+}
+
+/// A [`Solution`] that can show how it got its answers.
+pub trait Explain: Solution {
+    /// Solves `part` of `input`, keeping what's needed to check the answer by hand.
+    fn explain(input: &Self::Input, params: &Self::Params, part: u8) -> Explanation;
+}
+
+type Run =
+    Box<dyn Fn(&str, &[(String, String)], u8) -> Result<Explanation, RunError> + Send + Sync>;
+
+/// A day's [`Explain`] hook with its input and parameter types erased.
+pub struct Explainer {
+    pub year: u32,
+    pub day: u8,
+    run: Run,
+}
+
+impl Explainer {
+    /// Parses `input`, applies the parameter `overrides` and explains `part`.
+    pub fn run(
+        &self,
+        input: &str,
+        overrides: &[(String, String)],
+        part: u8,
+    ) -> Result<Explanation, RunError> {
+        (self.run)(input, overrides, part)
+    }
+}
+
+pub(crate) fn register<S: Explain + 'static>() -> Explainer {
+    Explainer {
+        year: S::YEAR,
+        day: S::DAY,
+        run: Box::new(|input, overrides, part| {
+            let params = S::Params::with(overrides.iter().map(|(k, v)| (k.as_str(), v.as_str())))?;
+            Ok(S::explain(&S::parse(input)?, &params, part))
+        }),
+    }
+}
+
+static EXPLAINERS: LazyLock<Vec<Explainer>> = LazyLock::new(year2024::explainers);
+
+/// Every day that can explain itself, in order.
+pub fn explainers() -> &'static [Explainer] {
+    &EXPLAINERS
+}
+
+pub fn explainer(year: u32, day: u8) -> Option<&'static Explainer> {
+    explainers().iter().find(|e| e.year == year && e.day == day)
+}
+
+/// Renders a witness as indented `key: value` lines, with `-` in front of list items.
+pub fn render_text(witness: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, witness, 0);
+    out
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => Some("none".to_string()),
+        Value::String(s) => Some(s.clone()),
+        Value::Array(items) if items.is_empty() => Some("[]".to_string()),
+        Value::Array(items) if items.iter().all(|v| v.is_number()) => Some(value.to_string()),
+        Value::Array(_) | Value::Object(_) => None,
+        other => Some(other.to_string()),
+    }
+}
+
+fn write_value(out: &mut String, value: &Value, indent: usize) {
+    let pad = " ".repeat(indent);
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                match scalar(value) {
+                    Some(s) => writeln!(out, "{pad}{key}: {s}").unwrap(),
+                    None => {
+                        writeln!(out, "{pad}{key}:").unwrap();
+                        write_value(out, value, indent + 2);
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                match scalar(item) {
+                    Some(s) => writeln!(out, "{pad}- {s}").unwrap(),
+                    None => {
+                        // Put the first line of the item after the dash, like YAML does.
+                        let mut nested = String::new();
+                        write_value(&mut nested, item, indent + 2);
+                        write!(out, "{pad}- {}", nested.trim_start()).unwrap();
+                    }
+                }
+            }
+        }
+        other => writeln!(out, "{pad}{}", scalar(other).unwrap()).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn renders_witnesses_as_text() {
+        let witness = json!({
+            "cost": 7036,
+            "start": [1, 13],
+            "segments": [{"facing": "East", "steps": 2}, {"facing": "North", "steps": 3}],
+            "skipped": null,
+        });
+        assert_eq!(
+            render_text(&witness),
+            "cost: 7036\n\
+             start: [1,13]\n\
+             segments:\n  \
+               - facing: East\n    \
+                 steps: 2\n  \
+               - facing: North\n    \
+                 steps: 3\n\
+             skipped: none\n"
+        );
+    }
+
+    #[test]
+    fn explanations_agree_with_the_solvers() {
+        let examples = [
+            (1, "3 4\n4 3\n2 5\n1 3\n3 9\n3 3"),
+            (2, "7 6 4 2 1\n1 2 7 8 9\n1 3 2 4 5"),
+            (7, "190: 10 19\n3267: 81 40 27\n83: 17 5"),
+            (
+                13,
+                "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400",
+            ),
+            (22, "1\n2\n3\n2024"),
+        ];
+        for (day, input) in examples {
+            for part in 1..=2 {
+                let explanation = explainer(2024, day).unwrap().run(input, &[], part).unwrap();
+                let solver = crate::runner::find(2024, day, part, None).unwrap();
+                assert_eq!(explanation.answer, solver.run(input).unwrap().answer);
+            }
+        }
+        let days: Vec<_> = explainers().iter().map(|e| e.day).collect();
+        assert_eq!(days, [1, 2, 3, 4, 5, 7, 13, 16, 21, 22]);
+    }
+}
@@ -0,0 +1,217 @@
+//! Made-up puzzle inputs, for benchmarks and stress tests that can't use the real ones.
+//!
+//! Every day implements [`Generate`]. Its knobs are declared like puzzle parameters, with
+//! defaults that give inputs about the size of the real thing. The same seed and knobs always
+//! give the same input.
+
+use std::sync::LazyLock;
+
+use crate::{
+    grid::Grid,
+    params::{ParamError, Params},
+    point::Point,
+    solution::Solution,
+    year2024,
+};
+
+/// SplitMix64: small, fast and stable across versions, so seeds keep giving the same inputs.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`. `n` must not be zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number in `low..=high`.
+    pub fn between(&mut self, low: isize, high: isize) -> isize {
+        low + self.below((high - low + 1) as usize) as isize
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// A perfect maze on the odd cells of a `size` by `size` grid (`size` is rounded up to odd),
+/// with walls as `#` and passages as `.`. Afterwards `loops` of the remaining inner walls
+/// between two passages are knocked out, so there is more than one way through.
+pub fn maze(rng: &mut Rng, size: usize, loops: f64) -> Grid<u8> {
+    let size = size.max(5) | 1;
+    let mut grid = Grid::new(size, size, b'#');
+    let start = Point::new(1, size as isize - 2);
+    grid[start] = b'.';
+    let mut stack = vec![start];
+    while let Some(&cell) = stack.last() {
+        let mut options = [(0, -2), (2, 0), (0, 2), (-2, 0)]
+            .map(|(x, y)| Point::new(cell.x + x, cell.y + y))
+            .into_iter()
+            .filter(|p| p.x > 0 && p.y > 0 && p.x < size as isize - 1 && p.y < size as isize - 1)
+            .filter(|p| grid[p] == b'#')
+            .collect::<Vec<_>>();
+        if options.is_empty() {
+            stack.pop();
+            continue;
+        }
+        rng.shuffle(&mut options);
+        let next = options[0];
+        grid[Point::new((cell.x + next.x) / 2, (cell.y + next.y) / 2)] = b'.';
+        grid[next] = b'.';
+        stack.push(next);
+    }
+    for point in grid.points().collect::<Vec<_>>() {
+        let inner = point.x > 0
+            && point.y > 0
+            && point.x < size as isize - 1
+            && point.y < size as isize - 1;
+        let between = |a: Point, b: Point| grid[a] == b'.' && grid[b] == b'.';
+        let (x, y) = (point.x, point.y);
+        if inner
+            && grid[point] == b'#'
+            && (between(Point::new(x - 1, y), Point::new(x + 1, y))
+                || between(Point::new(x, y - 1), Point::new(x, y + 1)))
+            && rng.chance(loops)
+        {
+            grid[point] = b'.';
+        }
+    }
+    grid
+}
+
+/// A [`Solution`] that can make up inputs of its own.
+pub trait Generate: Solution {
+    /// Sizes and other choices for the generated input. The defaults resemble a real input.
+    type Knobs: Params;
+
+    fn generate(rng: &mut Rng, knobs: &Self::Knobs) -> String;
+
+    /// Parameters the solver needs for an input made with `knobs`, for knobs that change the
+    /// puzzle itself, such as the size of the memory space on day 18.
+    fn params_for(_: &Self::Knobs) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+/// A generated input with the parameter overrides to solve it with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub params: Vec<(String, String)>,
+}
+
+type Run = Box<dyn Fn(u64, &[(String, String)]) -> Result<Generated, ParamError> + Send + Sync>;
+
+/// A day's [`Generate`] hook with its knob types erased.
+pub struct Generator {
+    pub year: u32,
+    pub day: u8,
+    /// Names of the knobs, see [`Generate::Knobs`].
+    pub knobs: &'static [&'static str],
+    run: Run,
+}
+
+impl Generator {
+    /// An input made from `seed` with the knobs named in `overrides` set to their values.
+    pub fn run(&self, seed: u64, overrides: &[(String, String)]) -> Result<Generated, ParamError> {
+        (self.run)(seed, overrides)
+    }
+}
+
+pub(crate) fn register<S: Generate + 'static>() -> Generator {
+    Generator {
+        year: S::YEAR,
+        day: S::DAY,
+        knobs: S::Knobs::NAMES,
+        run: Box::new(|seed, overrides| {
+            let knobs = S::Knobs::with(overrides.iter().map(|(k, v)| (k.as_str(), v.as_str())))?;
+            // Mix the day in, so one seed doesn't give every day the same stream.
+            let mut rng = Rng::new(seed ^ (S::DAY as u64) << 56);
+            Ok(Generated {
+                input: S::generate(&mut rng, &knobs),
+                params: S::params_for(&knobs),
+            })
+        }),
+    }
+}
+
+static GENERATORS: LazyLock<Vec<Generator>> = LazyLock::new(year2024::generators);
+
+/// Every day's generator, in order.
+pub fn generators() -> &'static [Generator] {
+    &GENERATORS
+}
+
+pub fn generator(year: u32, day: u8) -> Option<&'static Generator> {
+    generators().iter().find(|g| g.year == year && g.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeds_are_reproducible() {
+        let first: Vec<_> = (0..5)
+            .map({
+                let mut rng = Rng::new(42);
+                move |_| rng.below(1000)
+            })
+            .collect();
+        let mut rng = Rng::new(42);
+        assert_eq!(first, (0..5).map(|_| rng.below(1000)).collect::<Vec<_>>());
+        let a = generator(2024, 9).unwrap().run(7, &[]).unwrap();
+        assert_eq!(a, generator(2024, 9).unwrap().run(7, &[]).unwrap());
+        assert_ne!(a, generator(2024, 9).unwrap().run(8, &[]).unwrap());
+    }
+
+    #[test]
+    fn mazes_connect_every_passage() {
+        let mut rng = Rng::new(1);
+        let grid = maze(&mut rng, 21, 0.0);
+        let passages = grid.values().filter(|&&b| b == b'.').count();
+        let mut seen = grid.same_size_with(false);
+        let mut stack = vec![Point::new(1, 19)];
+        let mut reached = 0;
+        while let Some(p) = stack.pop() {
+            if seen[p] || grid[p] == b'#' {
+                continue;
+            }
+            seen[p] = true;
+            reached += 1;
+            stack.extend(grid.neighbours(&p));
+        }
+        assert_eq!(reached, passages);
+        // A perfect maze on a 10x10 lattice has 100 cells and 99 passages between them.
+        assert_eq!(passages, 199);
+    }
+
+    #[test]
+    fn rejects_unknown_knobs() {
+        let knob = [("width".to_string(), "3".to_string())];
+        assert!(generator(2024, 1).unwrap().run(1, &knob).is_err());
+    }
+}
@@ -0,0 +1,460 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    parse::{self, ParseError, ParseResult},
+    point::Point,
+};
+
+pub const CARDINALS: [Point; 4] = [
+    Point::new(0, -1),
+    Point::new(1, 0),
+    Point::new(0, 1),
+    Point::new(-1, 0),
+];
+
+pub const DIAGONALS: [Point; 4] = [
+    Point::new(1, -1),
+    Point::new(1, 1),
+    Point::new(-1, 1),
+    Point::new(-1, -1),
+];
+
+pub const ALL_DIRECTIONS: [Point; 8] = [
+    Point::new(0, -1),
+    Point::new(1, 0),
+    Point::new(0, 1),
+    Point::new(-1, 0),
+    Point::new(1, -1),
+    Point::new(1, 1),
+    Point::new(-1, 1),
+    Point::new(-1, -1),
+];
+
+/// A dense, row-major grid addressed by [`Point`], with `(0, 0)` in the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    items: Vec<T>,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: Point) -> &Self::Output {
+        &self[&index]
+    }
+}
+
+impl<T> Index<&Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: &Point) -> &Self::Output {
+        &self.items[self.expect_index(index)]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, index: Point) -> &mut Self::Output {
+        &mut self[&index]
+    }
+}
+
+impl<T> IndexMut<&Point> for Grid<T> {
+    fn index_mut(&mut self, index: &Point) -> &mut Self::Output {
+        let index = self.expect_index(index);
+        &mut self.items[index]
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            items: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// A grid of the same dimensions as this one, filled with `fill`.
+    pub fn same_size_with<U: Clone>(&self, fill: U) -> Grid<U> {
+        Grid::new(self.width, self.height, fill)
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_vec(items: Vec<T>, width: usize) -> Self {
+        let height = items.len().checked_div(width).unwrap_or(0);
+        Grid {
+            items,
+            width,
+            height,
+        }
+    }
+
+    /// Parses a block of text with one row per line, converting each byte with `f`, which
+    /// returns `None` for bytes that don't belong in the grid. Leading and trailing whitespace
+    /// on each line is ignored, as are blank lines, but every row must have the same width.
+    pub fn parse_with(input: &str, mut f: impl FnMut(u8) -> Option<T>) -> ParseResult<Self> {
+        let mut width = None;
+        let mut items = Vec::with_capacity(input.len());
+        for line in parse::lines(input) {
+            for (i, c) in line.char_indices() {
+                match c.is_ascii().then_some(c as u8).and_then(&mut f) {
+                    Some(value) => items.push(value),
+                    None => {
+                        return Err(ParseError::at(
+                            input,
+                            &line[i..i + c.len_utf8()],
+                            format!("unexpected character `{c}`"),
+                        ))
+                    }
+                }
+            }
+            let expected = *width.get_or_insert(line.len());
+            if line.len() != expected {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("expected a row of width {expected}, found {}", line.len()),
+                ));
+            }
+        }
+        Ok(Grid::from_vec(items, width.unwrap_or(0)))
+    }
+
+    /// Where `point` is stored, or `None` if it's off the grid.
+    pub fn index_of(&self, point: &Point) -> Option<usize> {
+        point.index_in(self.width, self.height)
+    }
+
+    fn expect_index(&self, point: &Point) -> usize {
+        self.index_of(point).unwrap_or_else(|| {
+            panic!(
+                "{point} is outside of the {} by {} grid",
+                self.width, self.height
+            )
+        })
+    }
+
+    pub fn point_of(&self, index: usize) -> Point {
+        Point::new((index % self.width) as isize, (index / self.width) as isize)
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        self.items.get(self.index_of(point)?)
+    }
+
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        let index = self.index_of(point)?;
+        self.items.get_mut(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as isize;
+        (0..self.items.len() as isize).map(move |i| Point::new(i % width, i / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.items.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.items.iter()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.items[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.items.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.items.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// The in-bounds points reached by adding each of `deltas` to `point`.
+    pub fn neighbours_in<'a>(
+        &'a self,
+        point: &Point,
+        deltas: &'a [Point],
+    ) -> impl Iterator<Item = Point> + 'a {
+        let point = *point;
+        deltas
+            .iter()
+            .map(move |d| point + d)
+            .filter(|p| self.contains(p))
+    }
+
+    /// In-bounds north, east, south and west neighbours.
+    pub fn neighbours(&self, point: &Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours_in(point, &CARDINALS)
+    }
+
+    /// In-bounds north east, south east, south west and north west neighbours.
+    pub fn diagonal_neighbours(&self, point: &Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours_in(point, &DIAGONALS)
+    }
+
+    /// In-bounds neighbours in all eight directions.
+    pub fn all_neighbours(&self, point: &Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours_in(point, &ALL_DIRECTIONS)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            items: self.items.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Renders the grid with one character per cell, one row per line.
+    pub fn render(&self, mut f: impl FnMut(Point, &T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for (point, value) in self.iter() {
+            if point.x == 0 && point.y > 0 {
+                out.push('\n');
+            }
+            out.push(f(point, value));
+        }
+        out
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    pub fn find(&self, value: &T) -> Option<Point> {
+        self.items
+            .iter()
+            .position(|v| v == value)
+            .map(|i| self.point_of(i))
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a {
+        self.iter()
+            .filter(move |(_, v)| *v == value)
+            .map(|(p, _)| p)
+    }
+}
+
+impl Grid<u8> {
+    pub fn parse(input: &str) -> ParseResult<Self> {
+        Grid::parse_with(input, Some)
+    }
+
+    /// Parses a grid that may only contain the bytes in `allowed`.
+    pub fn parse_only(input: &str, allowed: &[u8]) -> ParseResult<Self> {
+        Grid::parse_with(input, |b| allowed.contains(&b).then_some(b))
+    }
+
+    /// Finds the one `value` in a grid parsed from `input`, reporting that it is missing or
+    /// where it turns up a second time otherwise.
+    pub fn require(&self, input: &str, value: u8) -> ParseResult<Point> {
+        let mut found = self.find_all(&value);
+        let point = found.next().ok_or_else(|| {
+            ParseError::at_offset(
+                input,
+                input.trim_end().len(),
+                format!("expected a `{}` in the grid", value as char),
+            )
+        })?;
+        match found.next() {
+            Some(other) => Err(error_at(
+                input,
+                other,
+                format!("expected only one `{}` in the grid", value as char),
+            )),
+            None => Ok(point),
+        }
+    }
+}
+
+/// An error pointing at `point` of a grid parsed from `input`.
+pub fn error_at(input: &str, point: Point, message: impl Into<String>) -> ParseError {
+    let Some(Point { x, y }) = point.try_cast::<usize>() else {
+        return ParseError::at(input, input, message);
+    };
+    let row = parse::lines(input).nth(y).unwrap_or(input);
+    ParseError::at(input, row.get(x..x + 1).unwrap_or(row), message)
+}
+
+impl Grid<char> {
+    pub fn parse_chars(input: &str) -> ParseResult<Self> {
+        Grid::parse_with(input, |b| Some(char::from(b)))
+    }
+}
+
+impl Display for Grid<u8> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|_, &b| b as char))
+    }
+}
+
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|_, &c| c))
+    }
+}
+
+impl<'a, T> Grid<T> {
+    pub fn stride_iter(&'a self, start: Point, stride: Point) -> GridLineIter<'a, T> {
+        GridLineIter {
+            grid: self,
+            point: start,
+            stride,
+        }
+    }
+
+    pub fn north_iter(&'a self, start: Point) -> GridLineIter<'a, T> {
+        self.stride_iter(start, Point::new(0, -1))
+    }
+
+    pub fn east_iter(&'a self, start: Point) -> GridLineIter<'a, T> {
+        self.stride_iter(start, Point::new(1, 0))
+    }
+
+    pub fn south_iter(&'a self, start: Point) -> GridLineIter<'a, T> {
+        self.stride_iter(start, Point::new(0, 1))
+    }
+
+    pub fn west_iter(&'a self, start: Point) -> GridLineIter<'a, T> {
+        self.stride_iter(start, Point::new(-1, 0))
+    }
+
+    pub fn north_east_iter(&'a self, start: Point) -> GridLineIter<'a, T> {
+        self.stride_iter(start, Point::new(1, -1))
+    }
+
+    pub fn north_west_iter(&'a self, start: Point) -> GridLineIter<'a, T> {
+        self.stride_iter(start, Point::new(-1, -1))
+    }
+
+    pub fn south_east_iter(&'a self, start: Point) -> GridLineIter<'a, T> {
+        self.stride_iter(start, Point::new(1, 1))
+    }
+
+    pub fn south_west_iter(&'a self, start: Point) -> GridLineIter<'a, T> {
+        self.stride_iter(start, Point::new(-1, 1))
+    }
+
+    pub fn orthogonal_direction_iterators(&'a self, start: Point) -> [GridLineIter<'a, T>; 4] {
+        CARDINALS.map(|stride| self.stride_iter(start, stride))
+    }
+
+    pub fn diagonal_direction_iterators(&'a self, start: Point) -> [GridLineIter<'a, T>; 4] {
+        DIAGONALS.map(|stride| self.stride_iter(start, stride))
+    }
+
+    pub fn all_direction_iterators(&'a self, start: Point) -> [GridLineIter<'a, T>; 8] {
+        ALL_DIRECTIONS.map(|stride| self.stride_iter(start, stride))
+    }
+}
+
+/// Walks a grid from a starting point in steps of `stride` until it leaves the grid.
+pub struct GridLineIter<'a, T> {
+    grid: &'a Grid<T>,
+    point: Point,
+    stride: Point,
+}
+
+impl<'a, T> Iterator for GridLineIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.grid.get(&self.point)?;
+        self.point += self.stride;
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"abc
+    def"#;
+
+    #[test]
+    fn parses_and_renders() {
+        let grid = Grid::parse(EXAMPLE).unwrap();
+        assert_eq!((grid.width, grid.height), (3, 2));
+        assert_eq!(grid[Point::new(1, 1)], b'e');
+        assert_eq!(grid.to_string(), "abc\ndef");
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let error = Grid::parse("abc\nde\nfgh").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        let error = Grid::parse_only("..\n.x", b".").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        let error = Grid::parse("..\n.é").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.message, "unexpected character `é`");
+    }
+
+    #[test]
+    fn requires_exactly_one() {
+        let grid = Grid::parse("#S.\n..#").unwrap();
+        assert_eq!(grid.require("#S.\n..#", b'S'), Ok(Point::new(1, 0)));
+        assert!(grid.require("#S.\n..#", b'E').is_err());
+        let input = "#S.\n S.#";
+        let error = Grid::parse(input)
+            .unwrap()
+            .require(input, b'S')
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn bounds_checked_access() {
+        let grid = Grid::parse(EXAMPLE).unwrap();
+        assert_eq!(grid.get(&Point::new(2, 0)), Some(&b'c'));
+        assert_eq!(grid.get(&Point::new(3, 0)), None);
+        assert_eq!(grid.get(&Point::new(-1, 1)), None);
+        assert_eq!(grid.find(&b'f'), Some(Point::new(2, 1)));
+        assert_eq!(grid.find(&b'z'), None);
+    }
+
+    #[test]
+    fn neighbours_stay_inside() {
+        let grid = Grid::parse(EXAMPLE).unwrap();
+        let corner: Vec<_> = grid.neighbours(&Point::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.all_neighbours(&Point::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::parse(EXAMPLE).unwrap();
+        assert_eq!(grid.row(1), b"def");
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), b"cf");
+        assert_eq!(
+            grid.east_iter(Point::new(1, 0))
+                .copied()
+                .collect::<Vec<_>>(),
+            b"bc"
+        );
+    }
+}
@@ -1,29 +1,22 @@
-mod day25;
-mod day24;
-mod day23;
-mod day22;
-mod day21;
-mod day20;
-mod day19;
-mod day18;
-mod day17;
-mod day16;
-mod day15;
-mod day14;
-mod day13;
-mod day12;
-mod day1;
-mod day10;
-mod day11;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod point;
+pub mod combinators;
+pub mod explain;
+pub mod generate;
+pub mod grid;
+pub mod lists;
+pub mod metrics;
+pub mod params;
+pub mod parse;
+pub mod pattern;
+pub mod point;
+pub mod precedence;
+pub mod report;
+pub mod runner;
+pub mod search;
+pub mod solution;
+pub mod stream;
+pub mod visualize;
+pub mod year2024;
 use aoc_runner_derive::aoc_lib;
 
+// cargo-aoc only knows one year per crate, so it runs 2024. Every year is in `runner`.
 aoc_lib! { year = 2024 }
@@ -0,0 +1,308 @@
+//! Comparing columns of IDs that should hold the same values, such as the two lists of day 1
+//! or an export of the same records from two systems.
+//!
+//! [`Columns`] reads any number of whitespace separated columns and sorts each once.
+//! [`Columns::compare`] then pairs two of them up smallest to smallest, for the total distance
+//! between them, how similar they are and statistics on where the distance comes from.
+//!
+//! Any `i64` may appear, so distances are unsigned and totals are kept in 128 bits.
+
+use std::{
+    cmp::{Ordering, Reverse},
+    fmt::Display,
+};
+
+use crate::parse::{self, number, ParseError};
+
+/// Columns of numbers, each sorted.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Columns {
+    sorted: Vec<Vec<i64>>,
+}
+
+impl Columns {
+    /// Reads rows of whitespace separated numbers. Every row needs as many as the first one.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut sorted: Vec<Vec<i64>> = Vec::new();
+        for line in parse::lines(input) {
+            let row = line.split_whitespace().collect::<Vec<_>>();
+            if sorted.is_empty() {
+                sorted = vec![Vec::new(); row.len()];
+            } else if row.len() != sorted.len() {
+                let message = format!("expected {} numbers like the first line", sorted.len());
+                return Err(ParseError::at(input, line, message));
+            }
+            for (column, item) in sorted.iter_mut().zip(row) {
+                column.push(number(input, item)?);
+            }
+        }
+        Ok(Columns::sorted(sorted))
+    }
+
+    /// Columns from `columns`, which may come in any order but must be equally long.
+    pub fn new(columns: Vec<Vec<i64>>) -> Result<Self, Ragged> {
+        let expected = columns.first().map_or(0, Vec::len);
+        if let Some((column, c)) = (columns.iter().enumerate()).find(|(_, c)| c.len() != expected) {
+            return Err(Ragged {
+                column,
+                len: c.len(),
+                expected,
+            });
+        }
+        Ok(Columns::sorted(columns))
+    }
+
+    fn sorted(mut columns: Vec<Vec<i64>>) -> Self {
+        for column in &mut columns {
+            column.sort_unstable();
+        }
+        Columns { sorted: columns }
+    }
+
+    /// How many columns there are.
+    pub fn width(&self) -> usize {
+        self.sorted.len()
+    }
+
+    /// How many numbers each column holds.
+    pub fn len(&self) -> usize {
+        self.sorted.first().map_or(0, Vec::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Column `i`, smallest first.
+    pub fn column(&self, i: usize) -> Option<&[i64]> {
+        self.sorted.get(i).map(Vec::as_slice)
+    }
+
+    /// Pairs column `left` with column `right`, or `None` if either doesn't exist.
+    pub fn compare(&self, left: usize, right: usize) -> Option<Comparison<'_>> {
+        Some(Comparison {
+            left: self.column(left)?,
+            right: self.column(right)?,
+        })
+    }
+}
+
+/// A column that isn't as long as the first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ragged {
+    pub column: usize,
+    pub len: usize,
+    pub expected: usize,
+}
+
+impl Display for Ragged {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Ragged {
+            column,
+            len,
+            expected,
+        } = self;
+        write!(f, "column {column} holds {len} numbers, not {expected}")
+    }
+}
+
+/// Two sorted columns, the `i`th smallest of one paired with the `i`th smallest of the other.
+#[derive(Debug, Clone, Copy)]
+pub struct Comparison<'a> {
+    left: &'a [i64],
+    right: &'a [i64],
+}
+
+/// One pair of a [`Comparison`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pair {
+    /// Where the pair sits in sorted order, 0 for the two smallest numbers.
+    pub rank: usize,
+    pub left: i64,
+    pub right: i64,
+    pub distance: u64,
+}
+
+/// Distances from `low` up to and including `high`, and how many pairs are that far apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    pub low: u64,
+    pub high: u64,
+    pub count: usize,
+}
+
+impl<'a> Comparison<'a> {
+    pub fn pairs(&self) -> impl Iterator<Item = Pair> + 'a {
+        let (left, right) = (self.left, self.right);
+        left.iter()
+            .zip(right)
+            .enumerate()
+            .map(|(rank, (&left, &right))| Pair {
+                rank,
+                left,
+                right,
+                distance: right.abs_diff(left),
+            })
+    }
+
+    /// The sum of the distances between the pairs.
+    pub fn distance(&self) -> u128 {
+        self.pairs().map(|pair| pair.distance as u128).sum()
+    }
+
+    /// Every number found in both columns, with how often it appears on the left and on the
+    /// right, smallest first.
+    pub fn shared(&self) -> Vec<(i64, usize, usize)> {
+        let (left, right) = (self.left, self.right);
+        let (mut i, mut j) = (0, 0);
+        let mut shared = Vec::new();
+        while i < left.len() && j < right.len() {
+            match left[i].cmp(&right[j]) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    let id = left[i];
+                    let on_left = left[i..].iter().take_while(|&&l| l == id).count();
+                    let on_right = right[j..].iter().take_while(|&&r| r == id).count();
+                    shared.push((id, on_left, on_right));
+                    i += on_left;
+                    j += on_right;
+                }
+            }
+        }
+        shared
+    }
+
+    /// Every number on the left times how often it appears on the right, added up.
+    pub fn similarity(&self) -> i128 {
+        self.shared()
+            .into_iter()
+            .map(|(id, on_left, on_right)| score(id, on_left, on_right))
+            .sum()
+    }
+
+    /// The middle distance, or the mean of the middle two if there's an even number of pairs.
+    pub fn median(&self) -> Option<f64> {
+        let mut distances = self.pairs().map(|pair| pair.distance).collect::<Vec<_>>();
+        distances.sort_unstable();
+        let middle = distances.len() / 2;
+        match distances.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(distances[middle] as f64),
+            _ => Some((distances[middle - 1] as f64 + distances[middle] as f64) / 2.0),
+        }
+    }
+
+    /// How the distances spread over `buckets` equally wide ranges from zero to the largest.
+    pub fn histogram(&self, buckets: usize) -> Vec<Bucket> {
+        let largest = self.pairs().map(|pair| pair.distance).max().unwrap_or(0);
+        let buckets = buckets.max(1) as u128;
+        // Worked out in 128 bits, as the last bucket may end past the largest `u64`.
+        let size = largest as u128 / buckets + 1;
+        let mut histogram = (0..buckets)
+            .map(|i| Bucket {
+                low: (i * size).min(u64::MAX as u128) as u64,
+                high: ((i + 1) * size - 1).min(u64::MAX as u128) as u64,
+                count: 0,
+            })
+            .collect::<Vec<_>>();
+        for pair in self.pairs() {
+            histogram[(pair.distance as u128 / size) as usize].count += 1;
+        }
+        histogram
+    }
+
+    /// The `n` pairs that add the most to the distance, furthest apart first.
+    pub fn top_contributors(&self, n: usize) -> Vec<Pair> {
+        let mut pairs = self.pairs().collect::<Vec<_>>();
+        pairs.sort_by_key(|pair| (Reverse(pair.distance), pair.rank));
+        pairs.truncate(n);
+        pairs
+    }
+}
+
+/// What an ID adds to the similarity when it appears `on_left` and `on_right` times.
+pub fn score(id: i64, on_left: usize, on_right: usize) -> i128 {
+    id as i128 * on_left as i128 * on_right as i128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "3 4 9\n4 3 9\n2 5 1\n1 3 1\n3 9 1\n3 3 8";
+
+    #[test]
+    fn compares_any_two_columns() {
+        let columns = Columns::parse(EXAMPLE).unwrap();
+        assert_eq!((columns.width(), columns.len()), (3, 6));
+        let first = columns.compare(0, 1).unwrap();
+        assert_eq!((first.distance(), first.similarity()), (11, 31));
+        let last = columns.compare(1, 2).unwrap();
+        assert_eq!(last.distance(), 14);
+        assert_eq!(last.similarity(), 18);
+        assert_eq!(last.shared(), [(9, 1, 2)]);
+        assert!(columns.compare(0, 3).is_none());
+    }
+
+    #[test]
+    fn summarises_the_distances() {
+        let columns = Columns::parse(EXAMPLE).unwrap();
+        let pairs = columns.compare(0, 1).unwrap();
+        assert_eq!(pairs.median(), Some(1.5));
+        let counts = pairs
+            .histogram(3)
+            .iter()
+            .map(|b| b.count)
+            .collect::<Vec<_>>();
+        assert_eq!(counts, [3, 2, 1]);
+        assert_eq!(
+            (pairs.histogram(3)[0].low, pairs.histogram(3)[0].high),
+            (0, 1)
+        );
+        let top = pairs.top_contributors(2);
+        assert_eq!((top[0].left, top[0].right, top[0].distance), (4, 9, 5));
+        assert_eq!((top[1].rank, top[1].distance), (0, 2));
+        assert!(Columns::default().compare(0, 0).is_none());
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let error = Columns::parse("1 2 3\n4 5\n").unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "expected 3 numbers like the first line")
+        );
+        let big = Columns::parse("4000000000 1\n-4000000000 2").unwrap();
+        assert_eq!(
+            big.compare(0, 1).unwrap().distance(),
+            4000000001 + 3999999998
+        );
+        assert_eq!(
+            Columns::new(vec![vec![1, 2], vec![3], vec![4, 5]]),
+            Err(Ragged {
+                column: 1,
+                len: 1,
+                expected: 2
+            })
+        );
+    }
+
+    #[test]
+    fn handles_the_extremes_of_i64() {
+        let extremes = format!("{} {}\n", i64::MAX, i64::MIN).repeat(3);
+        let columns = Columns::parse(&extremes).unwrap();
+        let pairs = columns.compare(0, 1).unwrap();
+        assert_eq!(pairs.pairs().next().unwrap().distance, u64::MAX);
+        assert_eq!(pairs.distance(), 3 * u64::MAX as u128);
+        assert_eq!(pairs.median(), Some(u64::MAX as f64));
+        let histogram = pairs.histogram(2);
+        assert_eq!((histogram[1].high, histogram[1].count), (u64::MAX, 3));
+        let same = Columns::parse(&format!("{0} {0}\n", i64::MIN).repeat(3)).unwrap();
+        let pairs = same.compare(0, 1).unwrap();
+        assert_eq!(
+            (pairs.distance(), pairs.similarity()),
+            (0, 9 * i64::MIN as i128)
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Counters for how much work a solver does, so an optimization can be judged by more than the
+//! clock.
+//!
+//! Solvers call [`record`] wherever they do a unit of work. Counting only happens with the
+//! `metrics` feature; without it [`record`] does nothing and compiles away. Counts are kept per
+//! thread, so whatever a `PARALLEL` variant hands to rayon isn't counted.
+
+use std::fmt::Display;
+
+/// The work done during one run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// States taken off a search's queue and expanded.
+    pub nodes_expanded: u64,
+    /// States pushed onto a search's heap or queue.
+    pub pushes: u64,
+    pub memo_hits: u64,
+    pub memo_misses: u64,
+    /// Deepest recursion reached.
+    pub max_depth: u64,
+    /// Passes through a solver's main loop.
+    pub iterations: u64,
+}
+
+impl Metrics {
+    /// Every counter with its name, in a fixed order.
+    pub fn counts(&self) -> [(&'static str, u64); 6] {
+        [
+            ("nodes_expanded", self.nodes_expanded),
+            ("pushes", self.pushes),
+            ("memo_hits", self.memo_hits),
+            ("memo_misses", self.memo_misses),
+            ("max_depth", self.max_depth),
+            ("iterations", self.iterations),
+        ]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Metrics::default()
+    }
+
+    /// Raises the recursion depth to `depth` if that's deeper than any seen so far.
+    pub fn reach(&mut self, depth: usize) {
+        self.max_depth = self.max_depth.max(depth as u64);
+    }
+}
+
+/// The counters that aren't zero, e.g. `nodes_expanded 120, pushes 340`.
+impl Display for Metrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut counts = self.counts().into_iter().filter(|&(_, n)| n > 0);
+        if let Some((name, n)) = counts.next() {
+            write!(f, "{name} {n}")?;
+        }
+        for (name, n) in counts {
+            write!(f, ", {name} {n}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "metrics")]
+thread_local! {
+    static CURRENT: std::cell::Cell<Metrics> = std::cell::Cell::new(Metrics::default());
+}
+
+/// Updates the counters of the run in progress on this thread.
+#[cfg(feature = "metrics")]
+pub fn record(update: impl FnOnce(&mut Metrics)) {
+    CURRENT.with(|current| {
+        let mut metrics = current.get();
+        update(&mut metrics);
+        current.set(metrics);
+    });
+}
+
+#[cfg(not(feature = "metrics"))]
+#[inline(always)]
+pub fn record(_: impl FnOnce(&mut Metrics)) {}
+
+/// Runs `f` and returns what it recorded. Counts from around the call are left as they were.
+#[cfg(feature = "metrics")]
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Metrics) {
+    let outer = CURRENT.replace(Metrics::default());
+    let result = f();
+    (result, CURRENT.replace(outer))
+}
+
+#[cfg(not(feature = "metrics"))]
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Metrics) {
+    (f(), Metrics::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_one_run_at_a_time() {
+        let (inner, outer) = measure(|| {
+            record(|m| m.iterations += 1);
+            let ((), inner) = measure(|| {
+                record(|m| m.memo_hits += 2);
+                record(|m| m.reach(7));
+                record(|m| m.reach(3));
+            });
+            record(|m| m.iterations += 1);
+            inner
+        });
+        if cfg!(feature = "metrics") {
+            assert_eq!(inner.to_string(), "memo_hits 2, max_depth 7");
+            assert_eq!(outer.to_string(), "iterations 2");
+        } else {
+            assert!(inner.is_empty() && outer.is_empty());
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Puzzle parameters: values such as grid sizes or step counts that differ between the
+//! examples and the real inputs.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Display},
+};
+
+/// A day's tunable values. The defaults are the ones the real puzzle input uses.
+pub trait Params: Default + Clone + Debug + Send + Sync + 'static {
+    /// Names of the parameters, in declaration order.
+    const NAMES: &'static [&'static str];
+
+    /// Sets the parameter called `name` from its textual `value`.
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError>;
+
+    /// The defaults with every `(name, value)` in `overrides` applied in order.
+    fn with<'a>(
+        overrides: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, ParamError> {
+        let mut params = Self::default();
+        for (name, value) in overrides {
+            params.set(name, value)?;
+        }
+        Ok(params)
+    }
+}
+
+/// Days without parameters.
+impl Params for () {
+    const NAMES: &'static [&'static str] = &[];
+
+    fn set(&mut self, name: &str, _: &str) -> Result<(), ParamError> {
+        Err(ParamError::unknown(name, Self::NAMES))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamError {
+    pub name: String,
+    pub message: String,
+}
+
+impl ParamError {
+    pub fn new(name: &str, message: impl Into<String>) -> Self {
+        ParamError {
+            name: name.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn unknown(name: &str, known: &[&str]) -> Self {
+        let message = if known.is_empty() {
+            "this day takes no parameters".to_string()
+        } else {
+            format!("unknown parameter, expected one of {}", known.join(", "))
+        };
+        ParamError::new(name, message)
+    }
+}
+
+impl Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "parameter `{}`: {}", self.name, self.message)
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// Declares a parameters struct with a default for every field and implements [`Params`] for
+/// it, parsing values with `FromStr`.
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $($(#[$field_meta:meta])* $field:ident: $ty:ty = $default:expr,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $name {
+            $($(#[$field_meta])* pub $field: $ty,)*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    $($field: $default,)*
+                }
+            }
+        }
+
+        impl $crate::params::Params for $name {
+            const NAMES: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            fn set(&mut self, name: &str, value: &str) -> Result<(), $crate::params::ParamError> {
+                match name {
+                    $(stringify!($field) => {
+                        self.$field = value.trim().parse().map_err(|_| {
+                            $crate::params::ParamError::new(
+                                name,
+                                format!("`{value}` is not a valid {}", stringify!($ty)),
+                            )
+                        })?
+                    })*
+                    _ => return Err($crate::params::ParamError::unknown(name, Self::NAMES)),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+pub(crate) use params;
+
+/// Parameter overrides per day, as read from a config file.
+pub type Overrides = BTreeMap<u8, Vec<(String, String)>>;
+
+/// Reads overrides from TOML with one table per day:
+///
+/// ```toml
+/// [day14]
+/// width = 11
+/// height = 7
+/// ```
+pub fn parse_config(text: &str) -> Result<Overrides, String> {
+    let table: toml::Table = text.parse().map_err(|e| format!("{e}"))?;
+    let mut overrides = Overrides::new();
+    for (day, values) in table {
+        let number = day
+            .strip_prefix("day")
+            .and_then(|d| d.parse().ok())
+            .ok_or_else(|| format!("`{day}` is not a day, expected e.g. `[day14]`"))?;
+        let values = values
+            .as_table()
+            .ok_or_else(|| format!("`{day}` should be a table of parameters"))?;
+        let values = values.iter().map(|(name, value)| {
+            let value = match value {
+                toml::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            (name.clone(), value)
+        });
+        overrides.entry(number).or_default().extend(values);
+    }
+    Ok(overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    params! {
+        /// Something to set.
+        pub struct Example {
+            /// A size.
+            size: usize = 71,
+            name: String = "default".to_string(),
+        }
+    }
+
+    #[test]
+    fn sets_fields_by_name() {
+        let params = Example::with([("size", "7"), ("name", "small")]).unwrap();
+        assert_eq!(params.size, 7);
+        assert_eq!(params.name, "small");
+        assert_eq!(Example::NAMES, ["size", "name"]);
+    }
+
+    #[test]
+    fn rejects_bad_names_and_values() {
+        let error = Example::with([("size", "-1")]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "parameter `size`: `-1` is not a valid usize"
+        );
+        assert!(Example::with([("width", "1")]).is_err());
+        assert!(<()>::with([("width", "1")]).is_err());
+    }
+
+    #[test]
+    fn reads_config_tables() {
+        let config = parse_config("[day14]\nwidth = 11\n\n[day18]\nsize = \"7\"\n").unwrap();
+        assert_eq!(config[&14], [("width".to_string(), "11".to_string())]);
+        assert_eq!(config[&18], [("size".to_string(), "7".to_string())]);
+        assert!(parse_config("[fourteen]\nwidth = 11").is_err());
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// A problem found while parsing puzzle input, pointing at the offending line and column.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// Number of characters the error covers, at least one.
+    pub width: usize,
+    /// The full text of the offending line.
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error covering `fragment`, which must be a slice of `input`. Fragments from elsewhere
+    /// are reported at the end of the input.
+    pub fn at(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset + fragment.len() <= input.len())
+            .unwrap_or(input.len());
+        let mut error = Self::at_offset(input, offset, message);
+        error.width = fragment.chars().count().max(1);
+        error
+    }
+
+    /// An error pointing at the byte `offset` in `input`.
+    pub fn at_offset(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            width: 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Moves an error found while parsing `section` on its own to where `section` sits in
+    /// `input`, so the reported location is relative to the whole input.
+    pub fn rebase(self, input: &str, section: &str) -> Self {
+        let origin = ParseError::at(input, section, "");
+        ParseError {
+            line: origin.line + self.line - 1,
+            column: if self.line == 1 {
+                origin.column + self.column - 1
+            } else {
+                self.column
+            },
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.snippet.replace('\t', " "))?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width)
+        )
+    }
+}
+
+// cargo-aoc reports generator failures with `{:#?}`, so show the same diagnostic there.
+impl Debug for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `fragment`, a slice of `input`, as a number.
+pub fn number<T: FromStr>(input: &str, fragment: &str) -> ParseResult<T> {
+    fragment.parse().map_err(|_| {
+        ParseError::at(
+            input,
+            fragment,
+            format!("expected a number, found `{fragment}`"),
+        )
+    })
+}
+
+/// Parses every item of `fragment` separated by `separator` and/or whitespace as a number.
+pub fn numbers<T: FromStr>(input: &str, fragment: &str, separator: char) -> ParseResult<Vec<T>> {
+    fragment
+        .split(|c: char| c == separator || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| number(input, s))
+        .collect()
+}
+
+/// Splits `fragment` around the first `delimiter`, or reports where the delimiter was missing.
+pub fn split_once<'a>(
+    input: &str,
+    fragment: &'a str,
+    delimiter: &str,
+) -> ParseResult<(&'a str, &'a str)> {
+    fragment.split_once(delimiter).ok_or_else(|| {
+        ParseError::at(
+            input,
+            fragment,
+            format!("expected `{delimiter}` in `{fragment}`"),
+        )
+    })
+}
+
+/// The trimmed, non-blank lines of `input`.
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().map(str::trim).filter(|l| !l.is_empty())
+}
+
+/// Splits `input` into blocks separated by one or more blank lines. Lines holding only
+/// whitespace count as blank, and both `\n` and `\r\n` line endings are accepted.
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(s) = start.take() {
+                sections.push(input[s..end].trim_end());
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.len();
+        }
+        offset += line.len();
+    }
+    if let Some(s) = start {
+        sections.push(input[s..end].trim_end());
+    }
+    sections
+}
+
+/// Like [`sections`], but requires exactly `N` of them.
+pub fn exact_sections<const N: usize>(input: &str) -> ParseResult<[&str; N]> {
+    let sections = sections(input);
+    let found = sections.len();
+    sections.try_into().map_err(|_| {
+        ParseError::at_offset(
+            input,
+            input.trim_end().len(),
+            format!("expected {N} blank line separated sections, found {found}"),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_fragments() {
+        let input = "1 2\n3 x 5\n";
+        let error = number::<u32>(input, &input[6..7]).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.snippet, "3 x 5");
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected a number, found `x`\n  |\n2 | 3 x 5\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn rebases_section_errors() {
+        let input = "header\n\n1 2\n3 x";
+        let section = sections(input)[1];
+        let error = numbers::<u32>(section, section, ' ')
+            .unwrap_err()
+            .rebase(input, section);
+        assert_eq!((error.line, error.column), (4, 3));
+    }
+
+    #[test]
+    fn sections_accept_crlf_and_whitespace() {
+        let input = "a\r\nb\r\n  \r\n\r\n  c\r\n";
+        assert_eq!(sections(input), vec!["a\r\nb", "  c"]);
+        assert!(exact_sections::<3>(input).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+//! Finding patterns in a [`Grid`]: words read in a straight line, as in a word search, and 2D
+//! stencils with wildcards that may be turned or flipped.
+//!
+//! Both searches return every match with where it is and which way it lies, and [`highlight`]
+//! and [`frame`] draw the grid with only the matched cells showing.
+
+use std::fmt::Display;
+
+use crate::{
+    grid::Grid,
+    parse::ParseResult,
+    point::Point,
+    visualize::{Cell, Frame, FLOOR, GOAL},
+};
+
+/// The compass name of one of the eight unit steps, e.g. `north east` for `(1, -1)`.
+pub fn heading(direction: Point) -> &'static str {
+    match (direction.x.signum(), direction.y.signum()) {
+        (0, -1) => "north",
+        (1, -1) => "north east",
+        (1, 0) => "east",
+        (1, 1) => "south east",
+        (0, 1) => "south",
+        (-1, 1) => "south west",
+        (-1, 0) => "west",
+        (-1, -1) => "north west",
+        _ => "nowhere",
+    }
+}
+
+/// A word found in a straight line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordMatch {
+    /// Index of the word in the list searched for.
+    pub word: usize,
+    /// Where the first letter is.
+    pub start: Point,
+    /// The step from each letter to the next.
+    pub direction: Point,
+    pub len: usize,
+}
+
+impl WordMatch {
+    /// The cells the word covers, first letter first.
+    pub fn cells(&self) -> impl Iterator<Item = Point> {
+        let WordMatch {
+            start,
+            direction,
+            len,
+            ..
+        } = *self;
+        (0..len as isize).map(move |i| start + direction * i)
+    }
+}
+
+/// Every place one of `words` reads along one of `directions`, by word, then start in row
+/// major order, then direction in the order given. A word that reads the same both ways is
+/// found once per direction it's read in, but a word of one letter, which reads the same every
+/// way, is only found once per cell, lying along the first direction.
+pub fn find_words<T: PartialEq>(
+    grid: &Grid<T>,
+    words: &[&[T]],
+    directions: &[Point],
+) -> Vec<WordMatch> {
+    let mut matches = Vec::new();
+    for (word, letters) in words.iter().enumerate() {
+        let Some(first) = letters.first() else {
+            continue;
+        };
+        let directions = if letters.len() == 1 {
+            &directions[..directions.len().min(1)]
+        } else {
+            directions
+        };
+        for start in grid.find_all(first) {
+            for &direction in directions {
+                let line = grid.stride_iter(start, direction).take(letters.len());
+                if line.eq(letters.iter()) {
+                    matches.push(WordMatch {
+                        word,
+                        start,
+                        direction,
+                        len: letters.len(),
+                    });
+                }
+            }
+        }
+    }
+    matches
+}
+
+/// Which ways a [`Stencil`] may lie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Only as it was written.
+    Fixed,
+    /// Turned by any number of quarter turns.
+    Rotations,
+    /// Turned, and also mirrored left to right before turning.
+    RotationsAndReflections,
+}
+
+/// How a stencil was turned to match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Orientation {
+    /// Quarter turns clockwise, from 0 to 3.
+    pub quarter_turns: u8,
+    /// Whether it was mirrored left to right before turning.
+    pub reflected: bool,
+}
+
+/// E.g. `as written`, `turned 90°` or `reflected and turned 180°`.
+impl Display for Orientation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.reflected, self.quarter_turns) {
+            (false, 0) => write!(f, "as written"),
+            (true, 0) => write!(f, "reflected"),
+            (false, turns) => write!(f, "turned {}°", turns as u32 * 90),
+            (true, turns) => write!(f, "reflected and turned {}°", turns as u32 * 90),
+        }
+    }
+}
+
+/// A rectangular pattern whose cells either have to hold a value or may hold anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stencil<T> {
+    width: usize,
+    height: usize,
+    /// The cells that aren't wildcards, from the top left corner.
+    fixed: Vec<(Point, T)>,
+}
+
+impl<T: Clone> Stencil<T> {
+    /// A stencil shaped like `cells`, where `None` is a wildcard.
+    pub fn new(cells: &Grid<Option<T>>) -> Self {
+        Stencil {
+            width: cells.width,
+            height: cells.height,
+            fixed: cells
+                .iter()
+                .filter_map(|(point, cell)| Some((point, cell.clone()?)))
+                .collect(),
+        }
+    }
+
+    /// The stencil turned a quarter clockwise.
+    fn turned(&self) -> Self {
+        let height = self.height as isize;
+        Stencil {
+            width: self.height,
+            height: self.width,
+            fixed: (self.fixed.iter())
+                .map(|(p, value)| (Point::new(height - 1 - p.y, p.x), value.clone()))
+                .collect(),
+        }
+    }
+
+    /// The stencil mirrored left to right.
+    fn reflected(&self) -> Self {
+        let width = self.width as isize;
+        Stencil {
+            fixed: (self.fixed.iter())
+                .map(|(p, value)| (Point::new(width - 1 - p.x, p.y), value.clone()))
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+impl Stencil<char> {
+    /// Reads a stencil drawn one row per line, where `wildcard` matches anything.
+    pub fn parse(text: &str, wildcard: char) -> ParseResult<Self> {
+        let cells = Grid::parse_chars(text)?;
+        Ok(Stencil::new(&cells.map(|&c| (c != wildcard).then_some(c))))
+    }
+}
+
+/// A stencil found in a grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StencilMatch {
+    /// The top left corner of the stencil as it lies.
+    pub corner: Point,
+    pub orientation: Orientation,
+    /// The cells that had to match, leaving out the wildcards.
+    pub cells: Vec<Point>,
+}
+
+/// Every place `stencil` fits in `grid` lying any way `symmetry` allows, by orientation and
+/// then corner in row major order. Orientations that look the same, such as an `X` turned by
+/// a quarter, are only tried once, under the first one found.
+pub fn find_stencil<T: Clone + PartialEq>(
+    grid: &Grid<T>,
+    stencil: &Stencil<T>,
+    symmetry: Symmetry,
+) -> Vec<StencilMatch> {
+    let reflections: &[bool] = match symmetry {
+        Symmetry::RotationsAndReflections => &[false, true],
+        _ => &[false],
+    };
+    let turns = if symmetry == Symmetry::Fixed { 1 } else { 4 };
+    let mut tried: Vec<Stencil<T>> = Vec::new();
+    let mut matches = Vec::new();
+    for &reflected in reflections {
+        let mut oriented = if reflected {
+            stencil.reflected()
+        } else {
+            stencil.clone()
+        };
+        for quarter_turns in 0..turns {
+            if quarter_turns > 0 {
+                oriented = oriented.turned();
+            }
+            oriented.fixed.sort_by_key(|&(p, _)| p);
+            if tried.contains(&oriented)
+                || oriented.width > grid.width
+                || oriented.height > grid.height
+            {
+                continue;
+            }
+            tried.push(oriented.clone());
+            let orientation = Orientation {
+                quarter_turns,
+                reflected,
+            };
+            for y in 0..=grid.height - oriented.height {
+                for x in 0..=grid.width - oriented.width {
+                    let corner = Point::new(x as isize, y as isize);
+                    if (oriented.fixed.iter()).all(|(p, value)| grid[corner + p] == *value) {
+                        matches.push(StencilMatch {
+                            corner,
+                            orientation,
+                            cells: oriented.fixed.iter().map(|(p, _)| corner + p).collect(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+    matches
+}
+
+/// `grid` drawn with only the cells in `cells` showing, and `.` everywhere else.
+pub fn highlight<T: Copy + Into<char>>(
+    grid: &Grid<T>,
+    cells: impl IntoIterator<Item = Point>,
+) -> String {
+    let shown = shown(grid, cells);
+    grid.render(|point, &value| if shown[point] { value.into() } else { '.' })
+}
+
+/// `grid` as a [`Frame`], with the cells in `cells` picked out in colour.
+pub fn frame<T: Copy + Into<char>>(
+    grid: &Grid<T>,
+    cells: impl IntoIterator<Item = Point>,
+) -> Frame {
+    let shown = shown(grid, cells);
+    Frame::from_grid(grid, |point, &value| {
+        Cell::new(value.into(), if shown[point] { GOAL } else { FLOOR })
+    })
+}
+
+fn shown<T>(grid: &Grid<T>, cells: impl IntoIterator<Item = Point>) -> Grid<bool> {
+    let mut shown = Grid::new(grid.width, grid.height, false);
+    for point in cells {
+        if let Some(cell) = shown.get_mut(&point) {
+            *cell = true;
+        }
+    }
+    shown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{ALL_DIRECTIONS, CARDINALS};
+
+    const PUZZLE: &str = "CATS\nAXOX\nTOXO\nXXAX";
+
+    #[test]
+    fn finds_words_in_the_directions_asked_for() {
+        let grid = Grid::parse_chars(PUZZLE).unwrap();
+        let (cat, cats) = (['C', 'A', 'T'], ['C', 'A', 'T', 'S']);
+        let everywhere = find_words(&grid, &[&cat, &cats], &ALL_DIRECTIONS);
+        let found = everywhere
+            .iter()
+            .map(|m| (m.word, m.start, heading(m.direction)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            [
+                (0, Point::new(0, 0), "east"),
+                (0, Point::new(0, 0), "south"),
+                (1, Point::new(0, 0), "east"),
+            ]
+        );
+        assert_eq!(
+            everywhere[1].cells().collect::<Vec<_>>(),
+            [Point::new(0, 0), Point::new(0, 1), Point::new(0, 2)]
+        );
+        assert_eq!(find_words(&grid, &[&cat], &CARDINALS[2..]).len(), 1);
+        assert!(find_words(&grid, &[&[]], &ALL_DIRECTIONS).is_empty());
+        let letter = find_words(&grid, &[&['S']], &ALL_DIRECTIONS);
+        assert_eq!(letter.len(), 1);
+        assert_eq!(letter[0].cells().collect::<Vec<_>>(), [Point::new(3, 0)]);
+        assert!(find_words(&grid, &[&['S']], &[]).is_empty());
+    }
+
+    #[test]
+    fn turns_and_flips_stencils() {
+        let grid = Grid::parse_chars("ab.\n.cd\nd..").unwrap();
+        let stencil = Stencil::parse("ab\n?c", '?').unwrap();
+        let fixed = find_stencil(&grid, &stencil, Symmetry::Fixed);
+        assert_eq!(fixed.len(), 1);
+        assert_eq!(fixed[0].corner, Point::new(0, 0));
+        let l = Stencil::parse("ab\n?c\n??", '?').unwrap();
+        let flipped = find_stencil(&grid, &l, Symmetry::RotationsAndReflections);
+        let found = flipped
+            .iter()
+            .map(|m| (m.corner, m.orientation.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(found, [(Point::new(0, 0), "as written".to_string())]);
+        let cross = Stencil::parse("a.a\n.b.\na.a", '.').unwrap();
+        let square = Grid::parse_chars("aaa\naba\naaa").unwrap();
+        assert_eq!(
+            find_stencil(&square, &cross, Symmetry::RotationsAndReflections).len(),
+            1
+        );
+        let corner = Stencil::parse("dc\n.b", '.').unwrap();
+        let turned = find_stencil(&grid, &corner, Symmetry::Rotations);
+        assert_eq!(
+            (turned[0].corner, turned[0].orientation.to_string()),
+            (Point::new(1, 0), "turned 180°".to_string())
+        );
+    }
+
+    #[test]
+    fn highlights_the_matches() {
+        let grid = Grid::parse_chars(PUZZLE).unwrap();
+        let found = find_words(&grid, &[&['C', 'A', 'T', 'S']], &ALL_DIRECTIONS);
+        let cells = found.iter().flat_map(WordMatch::cells);
+        assert_eq!(highlight(&grid, cells), "CATS\n....\n....\n....");
+        let frame = frame(&grid, [Point::new(1, 1), Point::new(9, 9)]);
+        assert_eq!(frame.cells[Point::new(1, 1)], Cell::new('X', GOAL));
+        assert_eq!(frame.cells[Point::new(2, 1)], Cell::new('O', FLOOR));
+    }
+}
@@ -1,12 +1,12 @@
 use std::{
     fmt::Display,
     iter::successors,
-    ops::{Add, Mul, Sub},
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
 };
 
 use ahash::{HashMap, HashSet};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Direction {
     North,
     East,
@@ -25,65 +25,180 @@ impl Direction {
     }
 }
 
+/// A point on a plane. Coordinates are `isize` unless asked otherwise, as the grid puzzles
+/// step off the edge and need negative ones.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash, Default)]
-pub struct Point {
-    pub x: isize,
-    pub y: isize,
+pub struct Point<T = isize> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Display for Point {
+/// A point in space, with the same operators as [`Point`].
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash, Default)]
+pub struct Point3<T = isize> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Display> Display for Point<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {})", self.x, self.y)
     }
 }
 
-impl Add<Point> for &Point {
-    type Output = Point;
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// Implements `$op` and `$assign` coordinate by coordinate, for owned and borrowed operands.
+macro_rules! componentwise {
+    ($point:ident { $($field:ident),+ }, $op:ident::$method:ident, $assign:ident::$assign_method:ident) => {
+        impl<T: $op<Output = T>> $op for $point<T> {
+            type Output = $point<T>;
 
-    fn add(self, rhs: Point) -> Self::Output {
-        Point {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
+            fn $method(self, rhs: $point<T>) -> $point<T> {
+                $point { $($field: self.$field.$method(rhs.$field)),+ }
+            }
         }
-    }
+
+        impl<T: $op<Output = T> + Copy> $op<&$point<T>> for $point<T> {
+            type Output = $point<T>;
+
+            fn $method(self, rhs: &$point<T>) -> $point<T> {
+                self.$method(*rhs)
+            }
+        }
+
+        impl<T: $op<Output = T> + Copy> $op<$point<T>> for &$point<T> {
+            type Output = $point<T>;
+
+            fn $method(self, rhs: $point<T>) -> $point<T> {
+                (*self).$method(rhs)
+            }
+        }
+
+        impl<T: $op<Output = T> + Copy> $op<&$point<T>> for &$point<T> {
+            type Output = $point<T>;
+
+            fn $method(self, rhs: &$point<T>) -> $point<T> {
+                (*self).$method(*rhs)
+            }
+        }
+
+        impl<T: $op<Output = T> + Copy> $assign for $point<T> {
+            fn $assign_method(&mut self, rhs: $point<T>) {
+                *self = (*self).$method(rhs);
+            }
+        }
+
+        impl<T: $op<Output = T> + Copy> $assign<&$point<T>> for $point<T> {
+            fn $assign_method(&mut self, rhs: &$point<T>) {
+                *self = (*self).$method(*rhs);
+            }
+        }
+    };
 }
 
-impl Add<&Point> for Point {
-    type Output = Point;
-    fn add(self, rhs: &Point) -> Self::Output {
-        Point {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
+/// The operators, tuple conversions and checked casts shared by [`Point`] and [`Point3`].
+macro_rules! point {
+    ($point:ident { $($field:ident),+ }) => {
+        componentwise!($point { $($field),+ }, Add::add, AddAssign::add_assign);
+        componentwise!($point { $($field),+ }, Sub::sub, SubAssign::sub_assign);
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = $point<T>;
+
+            fn neg(self) -> $point<T> {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T> + Copy> Neg for &$point<T> {
+            type Output = $point<T>;
+
+            fn neg(self) -> $point<T> {
+                -*self
+            }
+        }
+
+        impl<T: Mul<Output = T> + Copy> Mul<T> for $point<T> {
+            type Output = $point<T>;
+
+            fn mul(self, rhs: T) -> $point<T> {
+                $point { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl<T: Mul<Output = T> + Copy> Mul<T> for &$point<T> {
+            type Output = $point<T>;
+
+            fn mul(self, rhs: T) -> $point<T> {
+                *self * rhs
+            }
+        }
+
+        impl<T> $point<T> {
+            pub const fn new($($field: T),+) -> Self {
+                $point { $($field),+ }
+            }
+
+            /// The same point with `U` coordinates, or `None` if one of them doesn't fit.
+            pub fn try_cast<U: TryFrom<T>>(self) -> Option<$point<U>> {
+                Some($point { $($field: U::try_from(self.$field).ok()?),+ })
+            }
         }
+    };
+}
+
+point!(Point { x, y });
+point!(Point3 { x, y, z });
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point { x, y }
     }
 }
 
-impl Sub<&Point> for &Point {
-    type Output = Point;
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
 
-    fn sub(self, rhs: &Point) -> Self::Output {
-        Point {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3 { x, y, z }
     }
 }
 
-impl Mul<isize> for Point {
-    type Output = Point;
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
 
-    fn mul(self, rhs: isize) -> Self::Output {
-        Point {
-            x: self.x * rhs,
-            y: self.y * rhs,
-        }
+impl<T> Point<T>
+where
+    usize: TryFrom<T>,
+{
+    /// Where the point sits in a row-major array of `width` by `height` items, or `None` if it
+    /// lies outside of it.
+    pub fn index_in(self, width: usize, height: usize) -> Option<usize> {
+        let Point { x, y } = self.try_cast::<usize>()?;
+        (x < width && y < height).then(|| y * width + x)
     }
 }
 
-impl Point {
-    pub fn new(x: isize, y: isize) -> Self {
-        Point { x, y }
+impl Point3 {
+    pub fn manhattan_distance(&self, other: &Point3) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
     }
+}
+
+impl Point {
     pub fn delta(&self, other: &Self) -> Point {
         other - self
     }
@@ -97,7 +212,7 @@ impl Point {
         other: &Self,
     ) -> std::iter::Successors<Point, impl FnMut(&Point) -> Option<Point>> {
         let delta = self.delta(other);
-        successors(Some(self - &delta), move |d| Some(d - &delta))
+        successors(Some(self - delta), move |d| Some(d - delta))
     }
 
     pub fn navigate(&self, direction: &Direction) -> Self {
@@ -122,14 +237,6 @@ impl Point {
         self + twice_distance
     }
 
-    pub fn index(&self, width: &usize) -> usize {
-        (self.y * *width as isize + self.x) as usize
-    }
-
-    pub fn inbounds(&self, width: &usize, height: &usize) -> bool {
-        self.x >= 0 && self.x < *width as isize && self.y >= 0 && self.y < *height as isize
-    }
-
     pub fn cardinal_neighbours(&self) -> [Point; 4] {
         [
             Point::new(self.x, self.y - 1),
@@ -168,3 +275,43 @@ impl Point {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators_work_on_owned_and_borrowed_points() {
+        let a = Point::new(3, -2);
+        let b = Point::new(1, 4);
+        assert_eq!(a + b, Point::new(4, 2));
+        assert_eq!(a + b, a + b);
+        assert_eq!(a - b, Point::new(2, -6));
+        assert_eq!(-&a, Point::new(-3, 2));
+        assert_eq!(&b * 3, Point::new(3, 12));
+        let mut c = a;
+        c += &b;
+        c -= Point::new(1, 1);
+        assert_eq!(c, Point::new(3, 1));
+        let d = Point3::<i64>::new(1, 2, 3) * 2 - Point3::new(0, 0, 10);
+        assert_eq!(
+            (d.to_string(), d.into()),
+            ("(2, 4, -4)".to_string(), (2, 4, -4))
+        );
+    }
+
+    #[test]
+    fn casts_and_indices_are_checked() {
+        assert_eq!(Point::new(2, 1).index_in(3, 2), Some(5));
+        assert_eq!(Point::new(3, 0).index_in(3, 2), None);
+        assert_eq!(Point::new(-1, 1).index_in(3, 2), None);
+        assert_eq!(Point::<u8>::new(7, 9).index_in(10, 10), Some(97));
+        assert_eq!(Point::new(300, 2).try_cast::<u8>(), None);
+        assert_eq!(
+            Point::<i32>::from((5, 6)).try_cast(),
+            Some(Point::<usize>::new(5, 6))
+        );
+        let origin = Point3::default();
+        assert_eq!(origin.manhattan_distance(&Point3::new(1, -2, 3)), 6);
+    }
+}
@@ -0,0 +1,313 @@
+//! Ordering things by rules of the form `before|after`, such as the pages of day 5 or the
+//! steps of a build.
+//!
+//! Rules only apply between the items of one list: a rule that mentions something the list
+//! doesn't hold is ignored, and so is any chain of rules through it. The rules as a whole may
+//! well go round in circles, as long as they don't among the items of a list that gets sorted.
+//! No item may appear twice in one list.
+
+use std::{collections::BTreeSet, fmt::Display, hash::Hash, str::FromStr};
+
+use ahash::{HashMap, HashMapExt, HashSet};
+
+use crate::parse::{self, number, ParseResult};
+
+/// Which items have to come before which others.
+#[derive(Debug, Clone)]
+pub struct Precedence<N> {
+    after: HashMap<N, HashSet<N>>,
+}
+
+impl<N> Default for Precedence<N> {
+    fn default() -> Self {
+        Precedence {
+            after: HashMap::new(),
+        }
+    }
+}
+
+/// Items whose rules go round in a circle, each one to come before the next and the last
+/// before the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: Display> Display for Cycle<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for item in &self.0 {
+            write!(f, "{item} before ")?;
+        }
+        match self.0.first() {
+            Some(first) => write!(f, "{first}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> FromIterator<(N, N)> for Precedence<N> {
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(rules: I) -> Self {
+        let mut precedence = Precedence::default();
+        for (before, after) in rules {
+            precedence.add(before, after);
+        }
+        precedence
+    }
+}
+
+impl<N: FromStr + Clone + Eq + Hash> Precedence<N> {
+    /// Reads one `before|after` rule per line.
+    pub fn parse(input: &str) -> ParseResult<Self> {
+        parse::lines(input)
+            .map(|line| {
+                let (before, after) = parse::split_once(input, line, "|")?;
+                Ok((number(input, before.trim())?, number(input, after.trim())?))
+            })
+            .collect()
+    }
+}
+
+impl<N: Clone + Eq + Hash> Precedence<N> {
+    pub fn add(&mut self, before: N, after: N) {
+        self.after.entry(before).or_default().insert(after);
+    }
+
+    /// Whether a rule puts `before` ahead of `after`.
+    pub fn requires(&self, before: &N, after: &N) -> bool {
+        self.after
+            .get(before)
+            .is_some_and(|following| following.contains(after))
+    }
+
+    /// Every rule that `order` breaks, as `(before, after)` with `after` listed first, in the
+    /// order `after` appears.
+    pub fn violations(&self, order: &[N]) -> Vec<(N, N)> {
+        let mut broken = Vec::new();
+        for (i, after) in order.iter().enumerate() {
+            for before in &order[i + 1..] {
+                if self.requires(before, after) {
+                    broken.push((before.clone(), after.clone()));
+                }
+            }
+        }
+        broken
+    }
+
+    pub fn is_ordered(&self, order: &[N]) -> bool {
+        order.iter().enumerate().all(|(i, after)| {
+            order[i + 1..]
+                .iter()
+                .all(|before| !self.requires(before, after))
+        })
+    }
+
+    /// For every item, the positions in `items` of the items that must follow it.
+    fn successors(&self, items: &[N]) -> Vec<Vec<usize>> {
+        let position = (items.iter().enumerate())
+            .map(|(i, item)| (item, i))
+            .collect::<HashMap<_, _>>();
+        items
+            .iter()
+            .map(|item| {
+                let Some(after) = self.after.get(item) else {
+                    return Vec::new();
+                };
+                let mut next = (after.iter())
+                    .filter_map(|a| position.get(a).copied())
+                    .collect::<Vec<_>>();
+                next.sort_unstable();
+                next
+            })
+            .collect()
+    }
+
+    /// `items` in an order that keeps every rule between them. Of the items free to go next,
+    /// the one listed first goes first, so items the rules don't order stay as they were.
+    pub fn sort(&self, items: &[N]) -> Result<Vec<N>, Cycle<N>> {
+        let successors = self.successors(items);
+        let mut waiting_on = vec![0; items.len()];
+        for &next in successors.iter().flatten() {
+            waiting_on[next] += 1;
+        }
+        let mut free = (0..items.len())
+            .filter(|&i| waiting_on[i] == 0)
+            .collect::<BTreeSet<_>>();
+        let mut sorted = Vec::with_capacity(items.len());
+        while let Some(i) = free.pop_first() {
+            sorted.push(items[i].clone());
+            for &next in &successors[i] {
+                waiting_on[next] -= 1;
+                if waiting_on[next] == 0 {
+                    free.insert(next);
+                }
+            }
+        }
+        if sorted.len() == items.len() {
+            return Ok(sorted);
+        }
+        // Everything left waits on something else that's left, so walking back from any of
+        // them has to come round to an item it has seen before.
+        let mut before = vec![None; items.len()];
+        for (i, next) in successors.iter().enumerate() {
+            for &j in next.iter().filter(|_| waiting_on[i] > 0) {
+                before[j] = Some(i);
+            }
+        }
+        let start = (0..items.len()).find(|&i| waiting_on[i] > 0);
+        let mut seen = HashSet::default();
+        let mut walk = Vec::new();
+        let mut at = start;
+        while let Some(i) = at.filter(|&i| seen.insert(i)) {
+            walk.push(i);
+            at = before[i];
+        }
+        let end = at.expect("a stuck item waits on another");
+        let from = walk.iter().position(|&i| i == end).unwrap_or(0);
+        let mut cycle = walk.split_off(from);
+        cycle.reverse();
+        let first = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap_or(0);
+        cycle.rotate_left(first);
+        Err(Cycle(cycle.into_iter().map(|i| items[i].clone()).collect()))
+    }
+
+    /// A cycle among the rules between `items`, starting from the item listed first, if there
+    /// is one.
+    pub fn cycle(&self, items: &[N]) -> Option<Cycle<N>> {
+        self.sort(items).err()
+    }
+
+    /// The fewest items that have to be taken out of `order` and put back elsewhere for it to
+    /// keep every rule: its length less the longest run of items, not necessarily next to each
+    /// other, that are already in a right order.
+    ///
+    /// Items that are already right among themselves can't have a chain of rules putting a
+    /// later one first either, so once the rules are followed through, the items that break
+    /// them form a partial order and the most that can stay put is its widest antichain. By
+    /// Dilworth's theorem that's the number of items less a maximum matching, so the number of
+    /// moves is the size of that matching.
+    pub fn moves_to_fix(&self, order: &[N]) -> Result<usize, Cycle<N>> {
+        self.sort(order)?;
+        let successors = self.successors(order);
+        // reaches[i][j]: order[i] has to come before order[j], directly or through others.
+        let mut reaches = vec![vec![false; order.len()]; order.len()];
+        for (i, reach) in reaches.iter_mut().enumerate() {
+            let mut stack = successors[i].clone();
+            while let Some(j) = stack.pop() {
+                if !std::mem::replace(&mut reach[j], true) {
+                    stack.extend(&successors[j]);
+                }
+            }
+        }
+        // An edge from each item to every earlier one it has to come before.
+        let edges = (0..order.len())
+            .map(|i| (0..i).filter(|&j| reaches[i][j]).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut matched = vec![None; order.len()];
+        let moves = (0..order.len())
+            .filter(|&i| augment(i, &edges, &mut matched, &mut vec![false; order.len()]))
+            .count();
+        Ok(moves)
+    }
+}
+
+/// Looks for an augmenting path from `i` in Kuhn's algorithm, matching along it if found.
+fn augment(
+    i: usize,
+    edges: &[Vec<usize>],
+    matched: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    for &j in &edges[i] {
+        if std::mem::replace(&mut visited[j], true) {
+            continue;
+        }
+        if matched[j].is_none_or(|k| augment(k, edges, matched, visited)) {
+            matched[j] = Some(i);
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    fn steps() -> Precedence<String> {
+        Precedence::parse("fetch|build\nconfigure | build\nbuild|test\nbuild|package\n").unwrap()
+    }
+
+    fn order(steps: &str) -> Vec<String> {
+        steps.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn orders_build_steps() {
+        let rules = steps();
+        let jumbled = order("test configure package build fetch");
+        assert_eq!(
+            rules.sort(&jumbled).unwrap(),
+            order("configure fetch build test package")
+        );
+        assert_eq!(
+            rules.violations(&jumbled),
+            [
+                ("build".to_string(), "test".to_string()),
+                ("build".to_string(), "package".to_string()),
+                ("fetch".to_string(), "build".to_string()),
+            ]
+        );
+        assert!(rules.is_ordered(&order("fetch configure build package")));
+        assert_eq!(rules.moves_to_fix(&jumbled), Ok(3));
+        // Without `build`, nothing ties `fetch` to `test`.
+        assert_eq!(rules.moves_to_fix(&order("test fetch")), Ok(0));
+    }
+
+    #[test]
+    fn finds_cycles_only_among_the_items() {
+        let rules = Precedence::from_iter([(1, 2), (2, 3), (3, 1), (3, 4)]);
+        assert_eq!(rules.sort(&[4, 3, 2]), Ok(vec![2, 3, 4]));
+        assert_eq!(rules.cycle(&[4, 1, 3, 2]), Some(Cycle(vec![1, 2, 3])));
+        assert_eq!(
+            Cycle(vec![1, 2, 3]).to_string(),
+            "1 before 2 before 3 before 1"
+        );
+        let error = Precedence::<u32>::parse("1|2\n3-4").unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "expected `|` in `3-4`")
+        );
+    }
+
+    #[test]
+    fn moves_the_fewest_items() {
+        // Every ordering of five items under a few partial orders, against trying every set of
+        // items to leave where they are.
+        let rule_sets = [
+            vec![(0, 1), (1, 2), (2, 3), (3, 4)],
+            vec![(0, 2), (1, 2), (2, 3), (2, 4)],
+            vec![(0, 1), (2, 3)],
+            vec![(4, 0), (0, 3), (1, 3), (3, 2)],
+        ];
+        for rules in rule_sets {
+            let rules = Precedence::from_iter(rules);
+            for order in (0..5).permutations(5) {
+                let kept = (0..=5)
+                    .rev()
+                    .find(|&size| {
+                        order.iter().combinations(size).any(|kept| {
+                            let kept = kept.into_iter().copied().collect::<Vec<_>>();
+                            let mut fixed = rules.clone();
+                            // Items left in place must stay in the order they are in.
+                            for (a, b) in kept.iter().tuple_windows() {
+                                fixed.add(*a, *b);
+                            }
+                            fixed.sort(&order).is_ok()
+                        })
+                    })
+                    .unwrap();
+                assert_eq!(rules.moves_to_fix(&order), Ok(5 - kept), "{order:?}");
+            }
+        }
+    }
+}
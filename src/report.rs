@@ -0,0 +1,392 @@
+//! Reports of a whole run: per solver, the timings, the answer, a hash of the input and whether
+//! the answer is the one recorded in `answers.toml`.
+//!
+//! Reports come as JSON or as a Markdown table. Neither holds anything that changes from one
+//! run to the next besides the timings, so two of them can be diffed to compare machines or
+//! compiler versions.
+
+use std::{fmt::Write, num::NonZero, time::Duration};
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use toml::Table;
+
+use crate::runner::{Outcome, RunError, Solver};
+
+/// The answers recorded for known inputs, in the layout of `answers.toml`: a table per year
+/// with a table per day, which names the input its answers are for by its `input_sha256`.
+#[derive(Debug, Clone, Default)]
+pub struct Answers(Table);
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        text.parse().map(Answers).map_err(|e| format!("{e}"))
+    }
+
+    fn day(&self, year: u32, day: u8) -> Option<&Table> {
+        let year = self.0.get(&year.to_string())?.as_table()?;
+        year.get(&format!("day{day}"))?.as_table()
+    }
+
+    /// The hash of the input the answers for `day` of `year` are for, if any are recorded.
+    pub fn input_sha256(&self, year: u32, day: u8) -> Option<&str> {
+        self.day(year, day)?.get("input_sha256")?.as_str()
+    }
+
+    /// The recorded answer for `solver` on the input hashing to `input_hash`, preferring one
+    /// specific to its variant. Answers recorded for any other input, or for no input in
+    /// particular, say nothing about this one.
+    pub fn expected(&self, solver: &Solver, input_hash: &str) -> Option<String> {
+        if self.input_sha256(solver.year, solver.day) != Some(input_hash) {
+            return None;
+        }
+        let day = self.day(solver.year, solver.day)?;
+        let part = format!("part{}", solver.part);
+        let answer = solver
+            .variant
+            .and_then(|variant| day.get(variant)?.as_table()?.get(&part))
+            .or_else(|| day.get(&part))?;
+        Some(match answer {
+            toml::Value::String(answer) => answer.clone(),
+            other => other.to_string(),
+        })
+    }
+}
+
+/// The SHA-256 of `input` in hex, as `sha256sum` prints it.
+pub fn input_hash(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// How a run's answer compares with the recorded one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    Match,
+    Mismatch,
+    /// Nothing is recorded for this input.
+    Unknown,
+    /// The solver failed, so there is no answer to compare.
+    Failed,
+}
+
+impl Check {
+    fn name(self) -> &'static str {
+        match self {
+            Check::Match => "match",
+            Check::Mismatch => "mismatch",
+            Check::Unknown => "unknown",
+            Check::Failed => "failed",
+        }
+    }
+}
+
+/// One solver's run.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub year: u32,
+    pub day: u8,
+    pub part: u8,
+    pub variant: Option<&'static str>,
+    pub input_hash: String,
+    pub result: Result<Outcome, RunError>,
+    pub expected: Option<String>,
+}
+
+impl Entry {
+    pub fn check(&self) -> Check {
+        match (&self.result, &self.expected) {
+            (Err(_), _) => Check::Failed,
+            (Ok(_), None) => Check::Unknown,
+            (Ok(outcome), Some(expected)) if outcome.answer == *expected => Check::Match,
+            (Ok(_), Some(_)) => Check::Mismatch,
+        }
+    }
+}
+
+/// What the solvers ran on and how they were built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Environment {
+    pub rustc: &'static str,
+    pub target: &'static str,
+    pub profile: &'static str,
+    pub features: Vec<&'static str>,
+    pub cpus: usize,
+}
+
+impl Environment {
+    pub fn current() -> Self {
+        Environment {
+            rustc: env!("AOC_RUSTC_VERSION"),
+            target: env!("AOC_TARGET"),
+            profile: if cfg!(debug_assertions) {
+                "debug"
+            } else {
+                "release"
+            },
+            features: [
+                cfg!(feature = "parallel").then_some("parallel"),
+                cfg!(feature = "png").then_some("png"),
+                cfg!(feature = "metrics").then_some("metrics"),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+            cpus: std::thread::available_parallelism().map_or(1, NonZero::get),
+        }
+    }
+}
+
+/// Every run of one invocation, in the order they happened.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub environment: Environment,
+    pub entries: Vec<Entry>,
+    pub total: Duration,
+}
+
+impl Report {
+    pub fn new(environment: Environment) -> Self {
+        Report {
+            environment,
+            entries: Vec::new(),
+            total: Duration::ZERO,
+        }
+    }
+
+    /// Adds the `result` of running `solver` on `input`, checked against `answers`.
+    pub fn record(
+        &mut self,
+        solver: &Solver,
+        input: &str,
+        result: Result<Outcome, RunError>,
+        answers: &Answers,
+    ) {
+        let input_hash = input_hash(input);
+        self.entries.push(Entry {
+            year: solver.year,
+            day: solver.day,
+            part: solver.part,
+            variant: solver.variant,
+            expected: answers.expected(solver, &input_hash),
+            input_hash,
+            result,
+        });
+    }
+
+    /// Whether every answer was found and none contradicts a recorded one.
+    pub fn passed(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|e| matches!(e.check(), Check::Match | Check::Unknown))
+    }
+
+    /// Durations are in nanoseconds.
+    pub fn to_json(&self) -> Value {
+        let env = &self.environment;
+        let entries = self.entries.iter().map(|entry| {
+            let mut run = json!({
+                "year": entry.year,
+                "day": entry.day,
+                "part": entry.part,
+                "variant": entry.variant,
+                "input_sha256": entry.input_hash,
+            });
+            match &entry.result {
+                Ok(outcome) => {
+                    run["parse_ns"] = json!(outcome.parse.as_nanos() as u64);
+                    run["solve_ns"] = json!(outcome.solve.as_nanos() as u64);
+                    run["answer"] = json!(outcome.answer);
+                    if !outcome.metrics.is_empty() {
+                        run["metrics"] = outcome.metrics.counts().into_iter().collect();
+                    }
+                }
+                Err(e) => run["error"] = json!(e.to_string()),
+            }
+            run["expected"] = json!(entry.expected);
+            run["check"] = json!(entry.check().name());
+            run
+        });
+        json!({
+            "environment": {
+                "rustc": env.rustc,
+                "target": env.target,
+                "profile": env.profile,
+                "features": env.features,
+                "cpus": env.cpus,
+            },
+            "runs": entries.collect::<Vec<_>>(),
+            "total_ns": self.total.as_nanos() as u64,
+        })
+    }
+
+    /// The environment as a list, followed by a table with a row per run.
+    pub fn to_markdown(&self) -> String {
+        let env = &self.environment;
+        let features = match env.features.as_slice() {
+            [] => "none".to_string(),
+            features => features.join(", "),
+        };
+        let mut out = String::new();
+        let _ = writeln!(out, "- rustc: {}", env.rustc);
+        let _ = writeln!(out, "- target: {} ({})", env.target, env.profile);
+        let _ = writeln!(out, "- features: {features}");
+        let _ = writeln!(out, "- cpus: {}", env.cpus);
+        let _ = writeln!(out, "- total: {:.2?}", self.total);
+        out.push('\n');
+        out.push_str("| Day | Part | Variant | Parse | Solve | Answer | Check | Input |\n");
+        out.push_str("|----:|-----:|---------|------:|------:|--------|-------|-------|\n");
+        for entry in &self.entries {
+            let (parse, solve, answer) = match &entry.result {
+                Ok(outcome) => (
+                    format!("{:.2?}", outcome.parse),
+                    format!("{:.2?}", outcome.solve),
+                    cell(&outcome.answer),
+                ),
+                Err(e) => (String::new(), String::new(), cell(&e.to_string())),
+            };
+            let check = match (entry.check(), &entry.expected) {
+                (Check::Mismatch, Some(expected)) => {
+                    format!("mismatch, expected {}", cell(expected))
+                }
+                (check, _) => check.name().to_string(),
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {parse} | {solve} | {answer} | {check} | `{}` |",
+                entry.day,
+                entry.part,
+                entry.variant.unwrap_or(""),
+                &entry.input_hash[..12],
+            );
+        }
+        out
+    }
+}
+
+/// `text` squashed onto one line, with the pipes that would end a table cell escaped.
+fn cell(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner;
+
+    const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3";
+
+    const ANSWERS: &str = r#"
+        [2024.day1]
+        input_sha256 = "b8f64a0b60b62dbdb5ef621a3a940d41026b380c477ecae09b7c45f1e7a1717b"
+        part1 = 11
+        part2 = "31"
+
+        [2024.day5]
+        input_sha256 = "abc"
+
+        [2024.day5.HASH]
+        part1 = 1
+    "#;
+
+    fn report() -> Report {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        let environment = Environment {
+            rustc: "rustc 1.0.0",
+            target: "x86_64-unknown-linux-gnu",
+            profile: "release",
+            features: vec![],
+            cpus: 4,
+        };
+        let mut report = Report::new(environment);
+        for solver in runner::solvers()
+            .iter()
+            .filter(|s| (s.year, s.day) == (2024, 1))
+        {
+            report.record(solver, EXAMPLE, solver.run(EXAMPLE), &answers);
+        }
+        let solver = runner::find(2024, 2, 1, None).unwrap();
+        report.record(solver, "1 x", solver.run("1 x"), &answers);
+        report
+    }
+
+    #[test]
+    fn hashes_like_sha256sum() {
+        assert_eq!(
+            input_hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn prefers_answers_for_the_variant() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        let hash = runner::find(2024, 5, 1, Some("HASH")).unwrap();
+        assert_eq!(answers.expected(hash, "abc").as_deref(), Some("1"));
+        assert_eq!(
+            answers.expected(runner::find(2024, 5, 1, None).unwrap(), "abc"),
+            None
+        );
+        assert!(Answers::parse("[day1").is_err());
+    }
+
+    #[test]
+    fn only_checks_answers_for_the_same_input() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        let day1 = runner::find(2024, 1, 1, None).unwrap();
+        let example = input_hash(EXAMPLE);
+        assert_eq!(answers.input_sha256(2024, 1), Some(example.as_str()));
+        assert_eq!(answers.expected(day1, &example).as_deref(), Some("11"));
+        assert_eq!(answers.expected(day1, &input_hash("1 2")), None);
+        let unhashed = Answers::parse("[2024.day1]\npart1 = 11").unwrap();
+        assert_eq!(unhashed.expected(day1, &example), None);
+        let mut report = Report::new(Environment::current());
+        report.record(day1, "1 2", day1.run("1 2"), &answers);
+        assert_eq!(report.entries[0].check(), Check::Unknown);
+        assert!(report.passed());
+    }
+
+    #[test]
+    fn checks_answers_against_the_recorded_ones() {
+        let report = report();
+        let checks = report.entries.iter().map(Entry::check).collect::<Vec<_>>();
+        assert_eq!(checks, [Check::Match, Check::Match, Check::Failed]);
+        assert!(!report.passed());
+        assert!(Report::new(report.environment.clone()).passed());
+    }
+
+    #[test]
+    fn reports_as_json() {
+        let json = report().to_json();
+        assert_eq!(json["environment"]["cpus"], 4);
+        let runs = json["runs"].as_array().unwrap();
+        assert_eq!(runs[1]["answer"], "31");
+        assert_eq!(runs[1]["check"], "match");
+        assert_eq!(runs[1]["input_sha256"].as_str().unwrap().len(), 64);
+        assert!(runs[1]["solve_ns"].is_u64());
+        assert_eq!(runs[2]["check"], "failed");
+        assert!(runs[2]["error"].as_str().unwrap().contains("expected"));
+    }
+
+    #[test]
+    fn reports_as_a_markdown_table() {
+        let markdown = report().to_markdown();
+        assert!(markdown.starts_with("- rustc: rustc 1.0.0\n"));
+        let rows = markdown
+            .lines()
+            .filter(|l| l.starts_with("| "))
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 4);
+        assert!(rows[1].starts_with("| 1 | 1 |  | "));
+        assert!(rows[1].contains("| 11 | match |"));
+        assert!(rows[3].contains("| failed |"));
+        assert_eq!(cell("a|b\n  c"), "a\\|b c");
+    }
+}
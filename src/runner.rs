@@ -0,0 +1,244 @@
+//! A registry of every solver, keyed by day, part and variant, callable without cargo-aoc.
+
+use std::{
+    error::Error,
+    fmt::Display,
+    path::PathBuf,
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    metrics::{self, Metrics},
+    params::{ParamError, Params},
+    parse::ParseError,
+    solution::{Solution, SolveError},
+    year2024,
+};
+
+type Run = Box<dyn Fn(&str, &[(String, String)]) -> Result<Outcome, RunError> + Send + Sync>;
+
+/// One part of a day, or a named variant of it, with its input and parameter types erased.
+pub struct Solver {
+    pub year: u32,
+    pub day: u8,
+    pub part: u8,
+    /// `None` for the default implementation of a part.
+    pub variant: Option<&'static str>,
+    /// Names of the day's parameters, see [`Solution::Params`].
+    pub params: &'static [&'static str],
+    run: Run,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunError {
+    Params(ParamError),
+    Parse(ParseError),
+    Solve(SolveError),
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::Params(e) => Display::fmt(e, f),
+            RunError::Parse(e) => Display::fmt(e, f),
+            RunError::Solve(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+impl Error for RunError {}
+
+impl From<ParamError> for RunError {
+    fn from(e: ParamError) -> Self {
+        RunError::Params(e)
+    }
+}
+
+impl From<ParseError> for RunError {
+    fn from(e: ParseError) -> Self {
+        RunError::Parse(e)
+    }
+}
+
+impl From<SolveError> for RunError {
+    fn from(e: SolveError) -> Self {
+        RunError::Solve(e)
+    }
+}
+
+/// The answer a solver gave and how long parsing and solving took.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub answer: String,
+    pub parse: Duration,
+    pub solve: Duration,
+    /// The work solving took, which is only counted with the `metrics` feature.
+    pub metrics: Metrics,
+}
+
+impl Solver {
+    /// Parses `input` and runs the solver on it with the default parameters.
+    pub fn run(&self, input: &str) -> Result<Outcome, RunError> {
+        (self.run)(input, &[])
+    }
+
+    /// Like [`Solver::run`], with the parameters named in `overrides` set to their values.
+    pub fn run_with(
+        &self,
+        input: &str,
+        overrides: &[(String, String)],
+    ) -> Result<Outcome, RunError> {
+        (self.run)(input, overrides)
+    }
+}
+
+impl Display for Solver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {} part {}", self.day, self.part)?;
+        if let Some(variant) = self.variant {
+            write!(f, " ({variant})")?;
+        }
+        Ok(())
+    }
+}
+
+fn timed<S: Solution + 'static>(
+    solve: fn(&S::Input, &S::Params) -> Result<String, SolveError>,
+) -> Run {
+    Box::new(move |input, overrides| {
+        let params = S::Params::with(overrides.iter().map(|(k, v)| (k.as_str(), v.as_str())))?;
+        let start = Instant::now();
+        let parsed = S::parse(input)?;
+        let solving = Instant::now();
+        let (answer, metrics) = metrics::measure(|| solve(&parsed, &params));
+        Ok(Outcome {
+            answer: answer?,
+            parse: solving - start,
+            solve: solving.elapsed(),
+            metrics,
+        })
+    })
+}
+
+pub(crate) fn register<S: Solution + 'static>(solvers: &mut Vec<Solver>) {
+    let mut parts = vec![
+        (
+            1,
+            None,
+            timed::<S>(|input, params| Ok(S::part1(input, params)?.to_string())),
+        ),
+        (
+            2,
+            None,
+            timed::<S>(|input, params| Ok(S::part2(input, params)?.to_string())),
+        ),
+    ];
+    for variant in S::variants() {
+        parts.push((variant.part, Some(variant.name), timed::<S>(variant.solve)));
+    }
+    solvers.extend(parts.into_iter().map(|(part, variant, run)| Solver {
+        year: S::YEAR,
+        day: S::DAY,
+        part,
+        variant,
+        params: S::Params::NAMES,
+        run,
+    }));
+}
+
+static SOLVERS: LazyLock<Vec<Solver>> = LazyLock::new(|| {
+    let mut solvers = Vec::new();
+    year2024::solvers(&mut solvers);
+    solvers.sort_by_key(|s| (s.year, s.day, s.part, s.variant.is_some()));
+    solvers
+});
+
+/// Every registered solver, ordered by year, day and part with the default before any variants.
+pub fn solvers() -> &'static [Solver] {
+    &SOLVERS
+}
+
+/// The most recent year with solvers, which the tools run unless told otherwise.
+pub fn latest_year() -> u32 {
+    solvers().iter().map(|s| s.year).max().unwrap_or_default()
+}
+
+/// Where cargo-aoc keeps the input for `day` of `year`.
+pub fn input_path(year: u32, day: u8) -> PathBuf {
+    PathBuf::from(format!("input/{year}/day{day}.txt"))
+}
+
+/// The solver for `day`/`part` of `year`, matching `variant` case-insensitively.
+pub fn find(year: u32, day: u8, part: u8, variant: Option<&str>) -> Option<&'static Solver> {
+    solvers().iter().find(|s| {
+        s.year == year
+            && s.day == day
+            && s.part == part
+            && match (s.variant, variant) {
+                (None, None) => true,
+                (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                _ => false,
+            }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_variants() {
+        assert_eq!(
+            find(2024, 5, 1, Some("hash")).unwrap().variant,
+            Some("HASH")
+        );
+        assert!(find(2024, 5, 2, Some("HASH")).is_none());
+        assert_eq!(
+            find(2024, 25, 1, None).unwrap().to_string(),
+            "day 25 part 1"
+        );
+    }
+
+    #[test]
+    fn registers_both_parts_of_every_day() {
+        for day in 1..=25 {
+            assert!(find(2024, day, 1, None).is_some() && find(2024, day, 2, None).is_some());
+        }
+        let defaults = solvers()
+            .iter()
+            .filter(|s| s.year == 2024 && s.variant.is_none());
+        assert_eq!(defaults.count(), 50);
+        assert_eq!(latest_year(), 2024);
+    }
+
+    #[test]
+    fn applies_parameter_overrides() {
+        let day11 = find(2024, 11, 1, None).unwrap();
+        assert_eq!(day11.params, ["part1_blinks", "part2_blinks"]);
+        let blinks = [("part1_blinks".to_string(), "6".to_string())];
+        assert_eq!(day11.run_with("125 17", &blinks).unwrap().answer, "22");
+        let unknown = [("blinks".to_string(), "6".to_string())];
+        assert!(matches!(
+            day11.run_with("125 17", &unknown),
+            Err(RunError::Params(_))
+        ));
+    }
+
+    #[test]
+    fn runs_through_the_parser() {
+        let outcome = find(2024, 1, 1, None).unwrap().run("3 4\n4 3\n").unwrap();
+        assert_eq!(outcome.answer, "0");
+        assert!(find(2024, 1, 1, None).unwrap().run("3 x\n").is_err());
+    }
+
+    #[test]
+    fn measures_the_work_of_a_run() {
+        let outcome = find(2024, 19, 2, None)
+            .unwrap()
+            .run("r, wr, b, g, bwu, rb, gb, br\n\nbrwrr\nbggr")
+            .unwrap();
+        assert_eq!(outcome.answer, "3");
+        assert_eq!(outcome.metrics.memo_hits > 0, cfg!(feature = "metrics"));
+    }
+}
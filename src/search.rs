@@ -0,0 +1,312 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    hash::Hash,
+};
+
+use ahash::{HashMap, HashSet};
+
+use crate::metrics;
+
+/// A puzzle that can be searched: every state knows which states it leads to and at what cost.
+pub trait Graph {
+    type State: Clone + Eq + Hash;
+
+    fn successors(&self, state: &Self::State) -> impl Iterator<Item = (Self::State, usize)>;
+}
+
+/// Everything a search learned about the states it settled.
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    /// Cost of the cheapest way from a start to each reached state.
+    pub distances: HashMap<S, usize>,
+    /// Every state that precedes the key on some cheapest path, so ties are kept.
+    pub predecessors: HashMap<S, Vec<S>>,
+    /// Goal states that were reached at the cheapest goal cost, in the order they were found.
+    pub goals: Vec<S>,
+}
+
+impl<S> Default for Search<S> {
+    fn default() -> Self {
+        Search {
+            distances: HashMap::default(),
+            predecessors: HashMap::default(),
+            goals: Vec::new(),
+        }
+    }
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.distances.get(state).copied()
+    }
+
+    /// Cost of reaching the cheapest goal, if any goal was reached.
+    pub fn goal_distance(&self) -> Option<usize> {
+        self.goals.first().and_then(|goal| self.distance(goal))
+    }
+
+    /// One cheapest path from a start to `state`, both ends included.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.distances.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(previous) = self
+            .predecessors
+            .get(path.last().unwrap())
+            .and_then(|p| p.first())
+        {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// One cheapest path to the first goal that was found.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.goals.first().and_then(|goal| self.path_to(goal))
+    }
+
+    /// Every state that lies on at least one cheapest path to any of `ends`.
+    pub fn on_cheapest_paths<'a>(&self, ends: impl IntoIterator<Item = &'a S>) -> HashSet<S>
+    where
+        S: 'a,
+    {
+        let mut seen = HashSet::default();
+        let mut stack: Vec<S> = ends
+            .into_iter()
+            .filter(|end| self.distances.contains_key(end))
+            .cloned()
+            .collect();
+        while let Some(state) = stack.pop() {
+            if !seen.insert(state.clone()) {
+                continue;
+            }
+            if let Some(previous) = self.predecessors.get(&state) {
+                stack.extend(previous.iter().cloned());
+            }
+        }
+        seen
+    }
+}
+
+/// Breadth first search where every step costs one, whatever weight the graph gives it.
+///
+/// The search stops once every state as close as the nearest goal has been found, so pass
+/// `|_| false` to explore everything reachable.
+pub fn bfs<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::State>,
+    mut is_goal: impl FnMut(&G::State) -> bool,
+) -> Search<G::State> {
+    let mut search = Search::default();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !search.distances.contains_key(&start) {
+            search.distances.insert(start.clone(), 0);
+            metrics::record(|m| m.pushes += 1);
+            queue.push_back((start, 0));
+        }
+    }
+    let mut limit = usize::MAX;
+    while let Some((state, distance)) = queue.pop_front() {
+        if distance > limit {
+            break;
+        }
+        if is_goal(&state) {
+            limit = distance;
+            search.goals.push(state);
+            continue;
+        }
+        metrics::record(|m| m.nodes_expanded += 1);
+        for (next, _) in graph.successors(&state) {
+            match search.distances.get(&next) {
+                Some(&known) if known == distance + 1 => {
+                    search
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(state.clone());
+                }
+                Some(_) => {}
+                None => {
+                    search.distances.insert(next.clone(), distance + 1);
+                    search
+                        .predecessors
+                        .insert(next.clone(), vec![state.clone()]);
+                    metrics::record(|m| m.pushes += 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm over the graph's weights, with the same stopping rule as [`bfs`].
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::State>,
+    is_goal: impl FnMut(&G::State) -> bool,
+) -> Search<G::State> {
+    astar(graph, starts, |_| 0, is_goal)
+}
+
+/// A* search. `heuristic` must never overestimate the remaining cost to a goal, and should be
+/// consistent if the predecessor map is expected to hold every tied path. Ties are only kept
+/// for states that aren't settled yet, so zero cost cycles never end up in the predecessors.
+pub fn astar<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::State>,
+    mut heuristic: impl FnMut(&G::State) -> usize,
+    mut is_goal: impl FnMut(&G::State) -> bool,
+) -> Search<G::State> {
+    let mut search = Search::default();
+    // The heap holds indexes into `states` so that states don't need to be `Ord`.
+    let mut states = Vec::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if !search.distances.contains_key(&start) {
+            search.distances.insert(start.clone(), 0);
+            metrics::record(|m| m.pushes += 1);
+            queue.push(Reverse((heuristic(&start), 0, states.len())));
+            states.push(start);
+        }
+    }
+    let mut limit = usize::MAX;
+    let mut settled = HashSet::default();
+    while let Some(Reverse((estimate, distance, index))) = queue.pop() {
+        if estimate > limit {
+            break;
+        }
+        let state = states[index].clone();
+        if search.distances[&state] < distance || !settled.insert(state.clone()) {
+            continue;
+        }
+        if is_goal(&state) {
+            limit = distance;
+            search.goals.push(state);
+            continue;
+        }
+        metrics::record(|m| m.nodes_expanded += 1);
+        for (next, cost) in graph.successors(&state) {
+            let next_distance = distance + cost;
+            match search.distances.get(&next) {
+                Some(&known) if known < next_distance => {}
+                // A settled state, starts included, already has its cheapest paths. Adding a
+                // tie to it could only come back round a zero cost cycle.
+                Some(_) if settled.contains(&next) => {}
+                Some(&known) if known == next_distance => {
+                    search
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(state.clone());
+                }
+                _ => {
+                    search.distances.insert(next.clone(), next_distance);
+                    search
+                        .predecessors
+                        .insert(next.clone(), vec![state.clone()]);
+                    metrics::record(|m| m.pushes += 1);
+                    queue.push(Reverse((
+                        next_distance + heuristic(&next),
+                        next_distance,
+                        states.len(),
+                    )));
+                    states.push(next);
+                }
+            }
+        }
+    }
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a -1-> b -1-> d, a -3-> c -1-> d, a -2-> d
+    struct Diamond;
+
+    impl Graph for Diamond {
+        type State = char;
+
+        fn successors(&self, state: &char) -> impl Iterator<Item = (char, usize)> {
+            let edges: &[(char, usize)] = match state {
+                'a' => &[('b', 1), ('c', 3), ('d', 2)],
+                'b' => &[('d', 1)],
+                'c' => &[('d', 1)],
+                _ => &[],
+            };
+            edges.iter().copied()
+        }
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        let search = bfs(&Diamond, ['a'], |&s| s == 'd');
+        assert_eq!(search.goal_distance(), Some(1));
+        assert_eq!(search.path(), Some(vec!['a', 'd']));
+    }
+
+    #[test]
+    fn dijkstra_keeps_tied_paths() {
+        let search = dijkstra(&Diamond, ['a'], |&s| s == 'd');
+        assert_eq!(search.goal_distance(), Some(2));
+        let mut on_path: Vec<_> = search
+            .on_cheapest_paths(&search.goals)
+            .into_iter()
+            .collect();
+        on_path.sort();
+        assert_eq!(on_path, vec!['a', 'b', 'd']);
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let search = astar(&Diamond, ['a'], |&s| usize::from(s != 'd'), |&s| s == 'd');
+        assert_eq!(search.goal_distance(), Some(2));
+        assert_eq!(search.path().map(|p| p.len()), Some(2));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn counts_the_work() {
+        let (search, work) = crate::metrics::measure(|| dijkstra(&Diamond, ['a'], |_| false));
+        assert_eq!(search.distances.len(), 4);
+        // The path to `d` through `b` only ties the direct one, so `d` isn't pushed again.
+        assert_eq!((work.nodes_expanded, work.pushes), (4, 4));
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let search = dijkstra(&Diamond, ['b'], |&s| s == 'c');
+        assert_eq!(search.goal_distance(), None);
+        assert_eq!(search.distance(&'d'), Some(1));
+    }
+
+    /// a -0-> b -0-> c -0-> a, and c -1-> d
+    struct FreeLoop;
+
+    impl Graph for FreeLoop {
+        type State = char;
+
+        fn successors(&self, state: &char) -> impl Iterator<Item = (char, usize)> {
+            let edges: &[(char, usize)] = match state {
+                'a' => &[('b', 0)],
+                'b' => &[('c', 0), ('a', 0)],
+                'c' => &[('a', 0), ('d', 1)],
+                _ => &[],
+            };
+            edges.iter().copied()
+        }
+    }
+
+    #[test]
+    fn zero_cost_cycles_end_at_a_start() {
+        let search = dijkstra(&FreeLoop, ['a'], |&s| s == 'd');
+        assert_eq!(search.goal_distance(), Some(1));
+        assert_eq!(search.path(), Some(vec!['a', 'b', 'c', 'd']));
+        assert_eq!(search.path_to(&'a'), Some(vec!['a']));
+        assert_eq!(search.on_cheapest_paths(&search.goals).len(), 4);
+    }
+}
@@ -0,0 +1,63 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{params::Params, parse::ParseError};
+
+/// One day of the calendar: how to parse its input and solve both parts.
+///
+/// ```
+/// use aocrs_2024::{solution::Solution, year2024::day1::Day1};
+///
+/// let lists = Day1::parse("3 4\n4 3\n2 5").unwrap();
+/// assert_eq!(Day1::part1(&lists, &Default::default()).unwrap().to_string(), "3");
+/// ```
+pub trait Solution {
+    /// The parsed puzzle input, shared by both parts.
+    type Input;
+
+    /// Values the puzzle text fixes but the examples change, `()` if there are none.
+    type Params: Params;
+
+    const YEAR: u32;
+    const DAY: u8;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError>;
+
+    fn part2(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError>;
+
+    /// Alternative implementations of either part, solving the same parsed input.
+    fn variants() -> Vec<Variant<Self>> {
+        Vec::new()
+    }
+}
+
+/// A named alternative to a [`Solution`]'s `part1` or `part2`.
+pub struct Variant<S: Solution + ?Sized> {
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: fn(&S::Input, &S::Params) -> Result<String, SolveError>,
+}
+
+/// Why a part has no answer for an input that parsed fine, such as a program that can never
+/// print itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveError {
+    pub message: String,
+}
+
+impl SolveError {
+    pub fn new(message: impl Into<String>) -> Self {
+        SolveError {
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "no answer: {}", self.message)
+    }
+}
+
+impl Error for SolveError {}
@@ -0,0 +1,227 @@
+//! Streaming: solving a day from any reader a record at a time, for inputs too large to read
+//! into a string first.
+//!
+//! Days opt in by implementing [`Stream`], which folds every record into a running state and
+//! answers from that state once the input runs out. Only the state is kept, so memory depends on
+//! what the day has to remember rather than on how long the input is.
+
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{self, BufRead},
+    sync::LazyLock,
+};
+
+use crate::{
+    params::{ParamError, Params},
+    parse::ParseError,
+    runner::RunError,
+    solution::{Solution, SolveError},
+    year2024,
+};
+
+/// How a day's input splits into the records it folds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Records {
+    /// Every non-blank line on its own.
+    Lines,
+    /// Runs of lines separated by blank lines.
+    Sections,
+}
+
+/// A [`Solution`] whose answers can be built up one record at a time.
+pub trait Stream: Solution {
+    /// What's remembered between records.
+    type State: Default;
+
+    const RECORDS: Records = Records::Lines;
+
+    /// The parts that can be answered from the state alone.
+    const PARTS: &'static [u8] = &[1, 2];
+
+    /// Adds `record` to `state`. Errors point into `record`, the reader moves them to the right
+    /// line of the whole input.
+    fn fold(state: &mut Self::State, record: &str, params: &Self::Params)
+        -> Result<(), ParseError>;
+
+    /// The answer to `part`, one of [`Stream::PARTS`], once every record is in `state`.
+    fn answer(state: &Self::State, params: &Self::Params, part: u8) -> Result<String, SolveError>;
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Run(RunError),
+    Io(io::Error),
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamError::Run(e) => Display::fmt(e, f),
+            StreamError::Io(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+impl From<ParamError> for StreamError {
+    fn from(e: ParamError) -> Self {
+        StreamError::Run(e.into())
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(e: ParseError) -> Self {
+        StreamError::Run(e.into())
+    }
+}
+
+impl From<SolveError> for StreamError {
+    fn from(e: SolveError) -> Self {
+        StreamError::Run(e.into())
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+/// Folds every record `reader` holds into a fresh state.
+fn fold_all<S: Stream>(
+    reader: &mut dyn BufRead,
+    params: &S::Params,
+) -> Result<S::State, StreamError> {
+    let mut state = S::State::default();
+    let fold = |state: &mut S::State, record: &str, first_line: usize| {
+        S::fold(state, record, params).map_err(|e| ParseError {
+            line: first_line + e.line - 1,
+            ..e
+        })
+    };
+    let mut line = String::new();
+    let mut section = String::new();
+    let mut section_start = 0;
+    for number in 1.. {
+        line.clear();
+        // At the end of the input the line stays empty, which also closes the last section.
+        let read = reader.read_line(&mut line)?;
+        let text = line.trim_end_matches(['\n', '\r']);
+        let blank = text.trim().is_empty();
+        match S::RECORDS {
+            Records::Lines if !blank => fold(&mut state, text, number)?,
+            Records::Sections if !blank => {
+                if section.is_empty() {
+                    section_start = number;
+                } else {
+                    section.push('\n');
+                }
+                section.push_str(text);
+            }
+            Records::Sections if !section.is_empty() => {
+                fold(&mut state, &section, section_start)?;
+                section.clear();
+            }
+            _ => {}
+        }
+        if read == 0 {
+            break;
+        }
+    }
+    Ok(state)
+}
+
+type Run = Box<
+    dyn Fn(&mut dyn BufRead, &[(String, String)]) -> Result<Vec<(u8, String)>, StreamError>
+        + Send
+        + Sync,
+>;
+
+/// A day's [`Stream`] hook with its state and parameter types erased.
+pub struct Streamer {
+    pub year: u32,
+    pub day: u8,
+    /// See [`Stream::PARTS`].
+    pub parts: &'static [u8],
+    run: Run,
+}
+
+impl Streamer {
+    /// Reads `reader` to the end with the parameter `overrides` applied, and answers every part
+    /// in [`Streamer::parts`].
+    pub fn run(
+        &self,
+        reader: &mut dyn BufRead,
+        overrides: &[(String, String)],
+    ) -> Result<Vec<(u8, String)>, StreamError> {
+        (self.run)(reader, overrides)
+    }
+}
+
+pub(crate) fn register<S: Stream + 'static>() -> Streamer {
+    Streamer {
+        year: S::YEAR,
+        day: S::DAY,
+        parts: S::PARTS,
+        run: Box::new(|reader, overrides| {
+            let params = S::Params::with(overrides.iter().map(|(k, v)| (k.as_str(), v.as_str())))?;
+            let state = fold_all::<S>(reader, &params)?;
+            S::PARTS
+                .iter()
+                .map(|&part| Ok((part, S::answer(&state, &params, part)?)))
+                .collect()
+        }),
+    }
+}
+
+static STREAMERS: LazyLock<Vec<Streamer>> = LazyLock::new(year2024::streamers);
+
+/// Every day that can stream its input, in order.
+pub fn streamers() -> &'static [Streamer] {
+    &STREAMERS
+}
+
+pub fn streamer(year: u32, day: u8) -> Option<&'static Streamer> {
+    streamers().iter().find(|s| s.year == year && s.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream(day: u8, input: &str) -> Result<Vec<(u8, String)>, StreamError> {
+        streamer(2024, day).unwrap().run(&mut input.as_bytes(), &[])
+    }
+
+    #[test]
+    fn folds_lines_and_sections() {
+        let reports = "7 6 4 2 1\r\n1 2 7 8 9\n\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9";
+        let answers = stream(2, reports).unwrap();
+        assert_eq!(answers, [(1, "2".to_string()), (2, "4".to_string())]);
+        let machines = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\n\n\
+                        Button A: X+26, Y+66\nButton B: X+67, Y+21\nPrize: X=12748, Y=12176\n";
+        assert_eq!(stream(13, machines).unwrap()[0], (1, "280".to_string()));
+    }
+
+    #[test]
+    fn reports_errors_on_the_line_of_the_whole_input() {
+        let Err(StreamError::Run(RunError::Parse(e))) = stream(2, "1 2\n\n3 x 5\n") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((e.line, e.column, e.snippet.as_str()), (3, 3, "3 x 5"));
+        let machines = "Button A: X+1, Y+1\nButton B: X+2, Y+1\nPrize: X=3, Y=2\n\n\
+                        Button A: X+1, Y+1\nButton B: X+2, Y+2\nPrize: X=3, Y=3";
+        let Err(StreamError::Run(RunError::Parse(e))) = stream(13, machines) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(e.line, 5);
+        assert!(matches!(
+            streamer(2024, 1)
+                .unwrap()
+                .run(&mut &b"1 2\n\xff 3\n"[..], &[]),
+            Err(StreamError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,423 @@
+//! Draws the step-by-step grid simulations (days 6, 14, 15, 16 and 18) as ANSI terminal
+//! frames or as numbered PPM/PNG images.
+//!
+//! A day opts in by implementing [`Animate`], which hands a [`Frame`] to a [`Sink`] for every
+//! step it wants shown. The sink decides what to do with it.
+
+use std::{
+    error::Error,
+    fmt::Display,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::LazyLock,
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    grid::Grid,
+    params::{ParamError, Params},
+    parse::ParseError,
+    point::Point,
+    runner::RunError,
+    solution::Solution,
+    year2024,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+pub const BACKGROUND: Rgb = Rgb(16, 16, 24);
+pub const FLOOR: Rgb = Rgb(48, 48, 60);
+pub const WALL: Rgb = Rgb(140, 140, 150);
+pub const TRAIL: Rgb = Rgb(60, 110, 200);
+pub const ACTOR: Rgb = Rgb(250, 200, 40);
+pub const OBJECT: Rgb = Rgb(190, 120, 60);
+pub const GOAL: Rgb = Rgb(80, 200, 90);
+pub const DANGER: Rgb = Rgb(220, 50, 50);
+
+/// One grid square: the character drawn in the terminal and the colour used for both the
+/// character and the square in images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub color: Rgb,
+}
+
+impl Cell {
+    pub const fn new(glyph: char, color: Rgb) -> Self {
+        Cell { glyph, color }
+    }
+}
+
+/// The state of a simulation at one step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub cells: Grid<Cell>,
+    /// Shown under the grid in the terminal, e.g. the step number.
+    pub caption: String,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize) -> Self {
+        Frame {
+            cells: Grid::new(width, height, Cell::new(' ', BACKGROUND)),
+            caption: String::new(),
+        }
+    }
+
+    /// A frame the size of `grid`, with each square drawn by `f`.
+    pub fn from_grid<T>(grid: &Grid<T>, mut f: impl FnMut(Point, &T) -> Cell) -> Self {
+        let cells = grid.iter().map(|(p, v)| f(p, v)).collect();
+        Frame {
+            cells: Grid::from_vec(cells, grid.width),
+            caption: String::new(),
+        }
+    }
+
+    /// Draws over the square at `point`. Points outside the frame are ignored.
+    pub fn draw(&mut self, point: Point, glyph: char, color: Rgb) {
+        if let Some(cell) = self.cells.get_mut(&point) {
+            *cell = Cell::new(glyph, color);
+        }
+    }
+
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    /// The frame as 24-bit colour ANSI text, one row per line, caption last.
+    pub fn ansi(&self) -> String {
+        let mut out = String::new();
+        for row in self.cells.rows() {
+            let mut color = None;
+            for cell in row {
+                if color != Some(cell.color) {
+                    let Rgb(r, g, b) = cell.color;
+                    out.push_str(&format!("\x1b[38;2;{r};{g};{b}m"));
+                    color = Some(cell.color);
+                }
+                out.push(cell.glyph);
+            }
+            out.push_str("\x1b[0m\n");
+        }
+        out.push_str(&self.caption);
+        out
+    }
+
+    /// Packed RGB pixels with every square drawn `scale` pixels wide, row by row.
+    pub fn pixels(&self, scale: usize) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(self.cells.len() * scale * scale * 3);
+        for row in self.cells.rows() {
+            let line: Vec<u8> = row
+                .iter()
+                .flat_map(|cell| {
+                    let Rgb(r, g, b) = cell.color;
+                    [r, g, b].repeat(scale)
+                })
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        pixels
+    }
+
+    /// The frame as a binary PPM image.
+    pub fn ppm(&self, scale: usize) -> Vec<u8> {
+        let (width, height) = (self.cells.width * scale, self.cells.height * scale);
+        let mut out = format!("P6\n{width} {height}\n255\n").into_bytes();
+        out.extend(self.pixels(scale));
+        out
+    }
+}
+
+/// Where an animation's frames go.
+pub trait Sink {
+    fn frame(&mut self, frame: &Frame) -> io::Result<()>;
+
+    /// Called once after the last frame.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Keeps every frame, mostly for tests.
+impl Sink for Vec<Frame> {
+    fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+        self.push(frame.clone());
+        Ok(())
+    }
+}
+
+/// Redraws the terminal for each frame, pausing `delay` in between.
+pub struct Terminal<W: Write> {
+    pub out: W,
+    pub delay: Duration,
+}
+
+impl<W: Write> Sink for Terminal<W> {
+    fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+        write!(self.out, "\x1b[H\x1b[2J{}", frame.ansi())?;
+        self.out.flush()?;
+        thread::sleep(self.delay);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        writeln!(self.out)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    #[cfg(feature = "png")]
+    Png,
+}
+
+impl ImageFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            #[cfg(feature = "png")]
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+/// Writes each frame to `dir` as `frame00000.ppm`, `frame00001.ppm` and so on, drawing every
+/// square `scale` pixels wide.
+pub struct Images {
+    dir: PathBuf,
+    format: ImageFormat,
+    scale: usize,
+    written: usize,
+}
+
+impl Images {
+    /// Creates `dir` if it doesn't exist yet.
+    pub fn new(dir: impl Into<PathBuf>, format: ImageFormat, scale: usize) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Images {
+            dir,
+            format,
+            scale: scale.max(1),
+            written: 0,
+        })
+    }
+
+    pub fn written(&self) -> usize {
+        self.written
+    }
+}
+
+impl Sink for Images {
+    fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+        let path = self.dir.join(format!(
+            "frame{:05}.{}",
+            self.written,
+            self.format.extension()
+        ));
+        let mut out = BufWriter::new(File::create(path)?);
+        match self.format {
+            ImageFormat::Ppm => out.write_all(&frame.ppm(self.scale))?,
+            #[cfg(feature = "png")]
+            ImageFormat::Png => {
+                let mut encoder = png::Encoder::new(
+                    &mut out,
+                    (frame.cells.width * self.scale) as u32,
+                    (frame.cells.height * self.scale) as u32,
+                );
+                encoder.set_color(png::ColorType::Rgb);
+                encoder.set_depth(png::BitDepth::Eight);
+                encoder
+                    .write_header()
+                    .and_then(|mut writer| writer.write_image_data(&frame.pixels(self.scale)))
+                    .map_err(io::Error::other)?;
+            }
+        }
+        self.written += 1;
+        out.flush()
+    }
+}
+
+/// Passes on every `n`th frame, and the last one so the end state is always shown.
+pub struct Every<S: Sink> {
+    pub inner: S,
+    n: usize,
+    seen: usize,
+    skipped: Option<Frame>,
+}
+
+impl<S: Sink> Every<S> {
+    pub fn new(n: usize, inner: S) -> Self {
+        Every {
+            inner,
+            n: n.max(1),
+            seen: 0,
+            skipped: None,
+        }
+    }
+}
+
+impl<S: Sink> Sink for Every<S> {
+    fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+        self.seen += 1;
+        if (self.seen - 1).is_multiple_of(self.n) {
+            self.skipped = None;
+            self.inner.frame(frame)
+        } else {
+            self.skipped = Some(frame.clone());
+            Ok(())
+        }
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if let Some(frame) = self.skipped.take() {
+            self.inner.frame(&frame)?;
+        }
+        self.inner.finish()
+    }
+}
+
+/// A [`Solution`] whose simulation can be watched.
+pub trait Animate: Solution {
+    /// Runs the simulation on `input`, handing `sink` a frame for every step worth showing.
+    fn animate(input: &Self::Input, params: &Self::Params, sink: &mut dyn Sink) -> io::Result<()>;
+}
+
+#[derive(Debug)]
+pub enum VisualizeError {
+    Run(RunError),
+    Io(io::Error),
+}
+
+impl Display for VisualizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VisualizeError::Run(e) => Display::fmt(e, f),
+            VisualizeError::Io(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+impl Error for VisualizeError {}
+
+impl From<ParamError> for VisualizeError {
+    fn from(e: ParamError) -> Self {
+        VisualizeError::Run(e.into())
+    }
+}
+
+impl From<ParseError> for VisualizeError {
+    fn from(e: ParseError) -> Self {
+        VisualizeError::Run(e.into())
+    }
+}
+
+type Run = Box<
+    dyn Fn(&str, &[(String, String)], &mut dyn Sink) -> Result<(), VisualizeError> + Send + Sync,
+>;
+
+/// A day's [`Animate`] hook with its input and parameter types erased.
+pub struct Animation {
+    pub year: u32,
+    pub day: u8,
+    run: Run,
+}
+
+impl Animation {
+    /// Parses `input`, applies the parameter `overrides` and plays the animation into `sink`.
+    pub fn run(
+        &self,
+        input: &str,
+        overrides: &[(String, String)],
+        sink: &mut dyn Sink,
+    ) -> Result<(), VisualizeError> {
+        (self.run)(input, overrides, sink)
+    }
+}
+
+pub(crate) fn register<S: Animate + 'static>() -> Animation {
+    Animation {
+        year: S::YEAR,
+        day: S::DAY,
+        run: Box::new(|input, overrides, sink| {
+            let params = S::Params::with(overrides.iter().map(|(k, v)| (k.as_str(), v.as_str())))?;
+            let input = S::parse(input)?;
+            S::animate(&input, &params, sink)
+                .and_then(|_| sink.finish())
+                .map_err(VisualizeError::Io)
+        }),
+    }
+}
+
+static ANIMATIONS: LazyLock<Vec<Animation>> = LazyLock::new(year2024::animations);
+
+/// Every day that can be animated, in order.
+pub fn animations() -> &'static [Animation] {
+    &ANIMATIONS
+}
+
+pub fn animation(year: u32, day: u8) -> Option<&'static Animation> {
+    animations().iter().find(|a| a.year == year && a.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Frame {
+        let grid = Grid::from_vec(vec![true, false, false, true], 2);
+        Frame::from_grid(&grid, |_, &wall| {
+            if wall {
+                Cell::new('#', WALL)
+            } else {
+                Cell::new('.', FLOOR)
+            }
+        })
+        .with_caption("step 1")
+    }
+
+    #[test]
+    fn renders_ansi_and_ppm() {
+        let frame = checkerboard();
+        assert_eq!(
+            frame.ansi(),
+            "\x1b[38;2;140;140;150m#\x1b[38;2;48;48;60m.\x1b[0m\n\
+             \x1b[38;2;48;48;60m.\x1b[38;2;140;140;150m#\x1b[0m\nstep 1"
+        );
+        let ppm = frame.ppm(2);
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(ppm.len(), b"P6\n4 4\n255\n".len() + 4 * 4 * 3);
+        assert_eq!(&ppm[ppm.len() - 3..], [140, 140, 150]);
+    }
+
+    #[test]
+    fn every_keeps_the_last_frame() {
+        let mut sink = Every::new(3, Vec::new());
+        for step in 0..5 {
+            sink.frame(&Frame::new(1, 1).with_caption(step.to_string()))
+                .unwrap();
+        }
+        sink.finish().unwrap();
+        let captions: Vec<_> = sink.inner.iter().map(|f| f.caption.as_str()).collect();
+        assert_eq!(captions, ["0", "3", "4"]);
+    }
+
+    #[test]
+    fn registers_the_grid_simulations() {
+        let days: Vec<_> = animations().iter().map(|a| a.day).collect();
+        assert_eq!(days, [6, 14, 15, 16, 18]);
+        let mut frames = Vec::new();
+        assert!(matches!(
+            animation(2024, 6).unwrap().run("...", &[], &mut frames),
+            Err(VisualizeError::Run(RunError::Parse(_)))
+        ));
+        assert!(frames.is_empty());
+    }
+}
@@ -0,0 +1,263 @@
+//! Advent of Code 2024, and the lists that register its days with the shared registries.
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+use self::{
+    day1::Day1, day10::Day10, day11::Day11, day12::Day12, day13::Day13, day14::Day14, day15::Day15,
+    day16::Day16, day17::Day17, day18::Day18, day19::Day19, day2::Day2, day20::Day20, day21::Day21,
+    day22::Day22, day23::Day23, day24::Day24, day25::Day25, day3::Day3, day4::Day4, day5::Day5,
+    day6::Day6, day7::Day7, day8::Day8, day9::Day9,
+};
+use crate::{
+    explain::{self, Explainer},
+    generate::{self, Generator},
+    runner::{self, Solver},
+    stream::{self, Streamer},
+    visualize::{self, Animation},
+};
+
+pub(crate) fn solvers(solvers: &mut Vec<Solver>) {
+    runner::register::<Day1>(solvers);
+    runner::register::<Day2>(solvers);
+    runner::register::<Day3>(solvers);
+    runner::register::<Day4>(solvers);
+    runner::register::<Day5>(solvers);
+    runner::register::<Day6>(solvers);
+    runner::register::<Day7>(solvers);
+    runner::register::<Day8>(solvers);
+    runner::register::<Day9>(solvers);
+    runner::register::<Day10>(solvers);
+    runner::register::<Day11>(solvers);
+    runner::register::<Day12>(solvers);
+    runner::register::<Day13>(solvers);
+    runner::register::<Day14>(solvers);
+    runner::register::<Day15>(solvers);
+    runner::register::<Day16>(solvers);
+    runner::register::<Day17>(solvers);
+    runner::register::<Day18>(solvers);
+    runner::register::<Day19>(solvers);
+    runner::register::<Day20>(solvers);
+    runner::register::<Day21>(solvers);
+    runner::register::<Day22>(solvers);
+    runner::register::<Day23>(solvers);
+    runner::register::<Day24>(solvers);
+    runner::register::<Day25>(solvers);
+}
+
+pub(crate) fn generators() -> Vec<Generator> {
+    vec![
+        generate::register::<Day1>(),
+        generate::register::<Day2>(),
+        generate::register::<Day3>(),
+        generate::register::<Day4>(),
+        generate::register::<Day5>(),
+        generate::register::<Day6>(),
+        generate::register::<Day7>(),
+        generate::register::<Day8>(),
+        generate::register::<Day9>(),
+        generate::register::<Day10>(),
+        generate::register::<Day11>(),
+        generate::register::<Day12>(),
+        generate::register::<Day13>(),
+        generate::register::<Day14>(),
+        generate::register::<Day15>(),
+        generate::register::<Day16>(),
+        generate::register::<Day17>(),
+        generate::register::<Day18>(),
+        generate::register::<Day19>(),
+        generate::register::<Day20>(),
+        generate::register::<Day21>(),
+        generate::register::<Day22>(),
+        generate::register::<Day23>(),
+        generate::register::<Day24>(),
+        generate::register::<Day25>(),
+    ]
+}
+
+pub(crate) fn explainers() -> Vec<Explainer> {
+    vec![
+        explain::register::<Day1>(),
+        explain::register::<Day2>(),
+        explain::register::<Day3>(),
+        explain::register::<Day4>(),
+        explain::register::<Day5>(),
+        explain::register::<Day7>(),
+        explain::register::<Day13>(),
+        explain::register::<Day16>(),
+        explain::register::<Day21>(),
+        explain::register::<Day22>(),
+    ]
+}
+
+pub(crate) fn streamers() -> Vec<Streamer> {
+    vec![
+        stream::register::<Day1>(),
+        stream::register::<Day2>(),
+        stream::register::<Day7>(),
+        stream::register::<Day13>(),
+        stream::register::<Day14>(),
+        stream::register::<Day22>(),
+        stream::register::<Day23>(),
+    ]
+}
+
+pub(crate) fn animations() -> Vec<Animation> {
+    vec![
+        visualize::register::<Day6>(),
+        visualize::register::<Day14>(),
+        visualize::register::<Day15>(),
+        visualize::register::<Day16>(),
+        visualize::register::<Day18>(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::{any, proptest, Just, ProptestConfig, Strategy};
+
+    use super::*;
+
+    /// Small versions of each input, so the tests stay quick in debug builds. The property
+    /// tests draw each knob from one up to the value here.
+    const SMALL: &[(u8, &[(&str, usize)])] = &[
+        (1, &[("lines", 50)]),
+        (2, &[("reports", 50)]),
+        (3, &[("length", 2000)]),
+        (4, &[("size", 20)]),
+        (5, &[("pages", 15), ("updates", 20)]),
+        (6, &[("size", 20)]),
+        (7, &[("equations", 30), ("max_values", 8)]),
+        (8, &[("size", 20), ("frequencies", 6)]),
+        (9, &[("length", 201)]),
+        (10, &[("size", 20), ("trails", 10)]),
+        (12, &[("size", 30), ("regions", 40)]),
+        (13, &[("machines", 20)]),
+        (14, &[("robots", 150), ("width", 31), ("height", 29)]),
+        (15, &[("size", 15), ("moves", 500)]),
+        (16, &[("size", 21)]),
+        (18, &[("size", 15), ("bytes", 20)]),
+        (19, &[("towels", 40), ("designs", 20)]),
+        (20, &[("size", 31)]),
+        (22, &[("buyers", 20)]),
+        (23, &[("computers", 60), ("clique", 6), ("degree", 4)]),
+        (24, &[("bits", 12), ("swaps", 2)]),
+        (25, &[("locks", 20), ("keys", 20)]),
+    ];
+
+    /// The knobs of `day` that [`SMALL`] sizes.
+    fn knobs(day: u8) -> &'static [(&'static str, usize)] {
+        SMALL
+            .iter()
+            .find(|(d, _)| *d == day)
+            .map_or(&[], |(_, knobs)| knobs)
+    }
+
+    /// The knobs for a small input of `day`.
+    fn small(day: u8) -> Vec<(String, String)> {
+        knobs(day)
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    /// A day, its knobs no larger than [`SMALL`], and a seed. Shrinking heads for earlier days
+    /// and fewer of everything, so a failure ends up as small an input as still fails.
+    fn small_inputs() -> impl Strategy<Value = (u8, Vec<(String, String)>, u64)> {
+        (1..=25u8)
+            .prop_flat_map(|day| {
+                let values = knobs(day).iter().map(|&(_, most)| 1..=most).collect_vec();
+                (Just(day), values, any::<u64>())
+            })
+            .prop_map(|(day, values, seed)| {
+                let knobs = knobs(day)
+                    .iter()
+                    .zip(values)
+                    .map(|(&(k, _), v)| (k.to_string(), v.to_string()))
+                    .collect();
+                (day, knobs, seed)
+            })
+    }
+
+    /// Asserts that every solver for `day` gives the same answers on the input made from `seed`
+    /// and `knobs`.
+    fn variants_agree(day: u8, knobs: &[(String, String)], seed: u64) {
+        let generated = generate::generator(2024, day)
+            .unwrap()
+            .run(seed, knobs)
+            .unwrap();
+        for part in 1..=2 {
+            let answers = runner::solvers()
+                .iter()
+                .filter(|s| (s.year, s.day, s.part) == (2024, day, part))
+                .map(|s| {
+                    s.run_with(&generated.input, &generated.params)
+                        .unwrap()
+                        .answer
+                })
+                .collect::<Vec<_>>();
+            assert!(!answers.is_empty());
+            assert!(
+                answers.iter().all(|a| *a == answers[0]),
+                "day {day} part {part}, seed {seed}, knobs {knobs:?}: {answers:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn streaming_gives_the_same_answers() {
+        for streamer in streamers() {
+            let generator = generate::generator(streamer.year, streamer.day).unwrap();
+            let generated = generator.run(7, &small(streamer.day)).unwrap();
+            let streamed = streamer
+                .run(&mut generated.input.as_bytes(), &generated.params)
+                .unwrap();
+            for (part, answer) in streamed {
+                let solver = runner::find(streamer.year, streamer.day, part, None).unwrap();
+                let expected = solver.run_with(&generated.input, &generated.params);
+                assert_eq!(answer, expected.unwrap().answer, "{solver}");
+            }
+        }
+    }
+
+    #[test]
+    fn every_day_generates_inputs_its_solvers_agree_on() {
+        for generator in generators() {
+            variants_agree(generator.day, &small(generator.day), 1);
+        }
+        assert_eq!(generators().len(), 25);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(200))]
+
+        #[test]
+        fn variants_agree_on_random_inputs((day, knobs, seed) in small_inputs()) {
+            variants_agree(day, &knobs, seed);
+        }
+    }
+}
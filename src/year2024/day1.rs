@@ -0,0 +1,284 @@
+use std::{collections::BTreeMap, fmt::Display, iter::repeat_n};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use serde_json::json;
+
+use crate::{
+    explain::{Explain, Explanation},
+    generate::{Generate, Rng},
+    lists::{self, Columns, Comparison},
+    params::params,
+    parse::ParseError,
+    solution::{Solution, SolveError},
+    stream::Stream,
+};
+
+#[aoc_generator(day1)]
+pub fn input_generator_part_1(input: &str) -> Result<Columns, ParseError> {
+    let columns = Columns::parse(input)?;
+    if columns.width() == 1 {
+        let line = input
+            .lines()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or(input);
+        return Err(ParseError::at(input, line.trim(), "expected two numbers"));
+    }
+    Ok(columns)
+}
+
+params! {
+    /// Which two columns to compare, counted from 0. The puzzle has only these two.
+    pub struct Params {
+        left: usize = 0,
+        right: usize = 1,
+    }
+}
+
+fn compare<'a>(columns: &'a Columns, params: &Params) -> Result<Comparison<'a>, SolveError> {
+    columns.compare(params.left, params.right).ok_or_else(|| {
+        SolveError::new(format!(
+            "there are only {} columns to compare",
+            columns.width()
+        ))
+    })
+}
+
+#[aoc(day1, part1)]
+pub fn part1(input: &Columns) -> Result<u128, SolveError> {
+    Ok(compare(input, &Params::default())?.distance())
+}
+
+#[aoc(day1, part2)]
+pub fn part2(input: &Columns) -> Result<i128, SolveError> {
+    Ok(compare(input, &Params::default())?.similarity())
+}
+
+/// Day 1: Historian Hysteria.
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Columns;
+    type Params = Params;
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 1;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator_part_1(input)
+    }
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(compare(input, params)?.distance())
+    }
+
+    fn part2(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(compare(input, params)?.similarity())
+    }
+}
+
+/// Part 1 shows how the distances are spread and which pairs are furthest apart. Part 2 lists
+/// the IDs on both lists that score the most.
+impl Explain for Day1 {
+    fn explain(input: &Columns, params: &Params, part: u8) -> Explanation {
+        const SHOWN: usize = 10;
+        let columns = [params.left, params.right];
+        let Ok(pairs) = compare(input, params) else {
+            return Explanation::new(
+                "none",
+                json!({ "columns": columns, "width": input.width() }),
+            );
+        };
+        if part == 2 {
+            let mut shared = pairs.shared();
+            shared.sort_by_key(|&(id, on_left, on_right)| {
+                std::cmp::Reverse(lists::score(id, on_left, on_right))
+            });
+            let top = shared
+                .iter()
+                .take(SHOWN)
+                .map(|&(id, on_left, on_right)| {
+                    json!({ "id": id, "left": on_left, "right": on_right })
+                })
+                .collect::<Vec<_>>();
+            return Explanation::new(
+                pairs.similarity(),
+                json!({ "columns": columns, "shared": shared.len(), "top": top }),
+            );
+        }
+        let histogram = pairs
+            .histogram(SHOWN)
+            .iter()
+            .map(|b| json!({ "from": b.low, "to": b.high, "pairs": b.count }))
+            .collect::<Vec<_>>();
+        let furthest = pairs
+            .top_contributors(SHOWN)
+            .iter()
+            .map(|p| json!({ "left": p.left, "right": p.right, "distance": p.distance }))
+            .collect::<Vec<_>>();
+        Explanation::new(
+            pairs.distance(),
+            json!({
+                "columns": columns,
+                "pairs": input.len(),
+                "median": pairs.median(),
+                "histogram": histogram,
+                "furthest": furthest,
+            }),
+        )
+    }
+}
+
+/// How often each location ID appears in either list. Sorting is then just walking the keys.
+/// Every distinct ID is kept once with its count, so repeats cost nothing but a list of
+/// distinct IDs is still held in full.
+#[derive(Debug, Default)]
+pub struct Tally {
+    /// How many numbers the first line had, which every other line must match.
+    width: Option<usize>,
+    left: BTreeMap<i64, usize>,
+    right: BTreeMap<i64, usize>,
+}
+
+/// Every ID in `list` as often as it was seen, smallest first.
+fn sorted(list: &BTreeMap<i64, usize>) -> impl Iterator<Item = i64> + '_ {
+    list.iter().flat_map(|(&id, &n)| repeat_n(id, n))
+}
+
+impl Stream for Day1 {
+    type State = Tally;
+
+    fn fold(tally: &mut Tally, record: &str, params: &Params) -> Result<(), ParseError> {
+        let row = input_generator_part_1(record)?;
+        let width = *tally.width.get_or_insert(row.width());
+        if row.width() != width {
+            let message = format!("expected {width} numbers like the first line");
+            return Err(ParseError::at(record, record.trim(), message));
+        }
+        let (Some(left), Some(right)) = (row.column(params.left), row.column(params.right)) else {
+            let message = format!(
+                "expected numbers in columns {} and {}",
+                params.left, params.right
+            );
+            return Err(ParseError::at(record, record.trim(), message));
+        };
+        for (&x, &y) in left.iter().zip(right) {
+            *tally.left.entry(x).or_default() += 1;
+            *tally.right.entry(y).or_default() += 1;
+        }
+        Ok(())
+    }
+
+    fn answer(tally: &Tally, _: &Params, part: u8) -> Result<String, SolveError> {
+        Ok(match part {
+            1 => sorted(&tally.left)
+                .zip(sorted(&tally.right))
+                .map(|(x, y)| y.abs_diff(x) as u128)
+                .sum::<u128>()
+                .to_string(),
+            _ => tally
+                .left
+                .iter()
+                .map(|(&id, &n)| lists::score(id, n, *tally.right.get(&id).unwrap_or(&0)))
+                .sum::<i128>()
+                .to_string(),
+        })
+    }
+}
+
+params! {
+    /// How long the generated lists are, how many there are and how much they overlap.
+    pub struct Knobs {
+        lines: usize = 1000,
+        /// How many lists, side by side. The puzzle has two.
+        columns: usize = 2,
+        /// Chance that a number in a list after the first is copied from the first.
+        repeats: f64 = 0.3,
+    }
+}
+
+/// Lists of five digit location IDs. Some IDs in the later lists are copied from the first, so
+/// the similarity score isn't zero.
+impl Generate for Day1 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let left: Vec<_> = (0..knobs.lines)
+            .map(|_| rng.between(10000, 99999))
+            .collect();
+        left.iter()
+            .map(|l| {
+                let mut row = l.to_string();
+                for _ in 1..knobs.columns.max(1) {
+                    let r = if rng.chance(knobs.repeats) {
+                        *rng.pick(&left)
+                    } else {
+                        rng.between(10000, 99999)
+                    };
+                    row += &format!("   {r}");
+                }
+                row
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = input_generator_part_1(
+            r#"3   4
+            4   3
+            2   5
+            1   3
+            3   9
+            3   3"#,
+        )
+        .unwrap();
+        assert_eq!(part1(&input).unwrap(), 11);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = input_generator_part_1(
+            r#"3   4
+            4   3
+            2   5
+            1   3
+            3   9
+            3   3"#,
+        )
+        .unwrap();
+        assert_eq!(part2(&input).unwrap(), 31)
+    }
+
+    #[test]
+    fn compares_any_two_columns() {
+        let input = input_generator_part_1("3 4 1\n4 3 4\n2 5 3").unwrap();
+        let params = Params { left: 1, right: 2 };
+        assert_eq!(Day1::part1(&input, &params).unwrap().to_string(), "4");
+        assert_eq!(Day1::part2(&input, &params).unwrap().to_string(), "7");
+        let missing = Params {
+            right: 3,
+            ..Params::default()
+        };
+        assert!(Day1::part1(&input, &missing).is_err());
+        assert!(input_generator_part_1("1\n2").is_err());
+        let explanation = Day1::explain(&input, &params, 1);
+        assert_eq!(explanation.witness["furthest"][0]["distance"], 2);
+    }
+
+    #[test]
+    fn streamed_rows_match_the_first() {
+        let mut tally = Tally::default();
+        let params = Params::default();
+        Day1::fold(&mut tally, "3 4 1", &params).unwrap();
+        let error = Day1::fold(&mut tally, "4 3", &params).unwrap_err();
+        assert_eq!(error.message, "expected 3 numbers like the first line");
+        Day1::fold(&mut tally, "2 5 3", &params).unwrap();
+        assert_eq!(Day1::answer(&tally, &params, 1).unwrap(), "4");
+    }
+}
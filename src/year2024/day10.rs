@@ -0,0 +1,206 @@
+use std::fmt::Display;
+
+use crate::{
+    generate::{Generate, Rng},
+    grid::Grid,
+    params::params,
+    parse::ParseError,
+    point::Point,
+    solution::{Solution, SolveError},
+};
+use ahash::HashSet;
+use aoc_runner_derive::{aoc, aoc_generator};
+
+pub type TopoMap = Grid<u8>;
+
+/// Height given to tiles that can't be walked on, marked `.` in the examples.
+const IMPASSABLE: u8 = u8::MAX;
+
+#[aoc_generator(day10)]
+fn parse(input: &str) -> Result<TopoMap, ParseError> {
+    Grid::parse_with(input, |b| match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'.' => Some(IMPASSABLE),
+        _ => None,
+    })
+}
+
+fn valid_neighbours<'a>(guide_map: &'a TopoMap, point: &Point) -> impl Iterator<Item = Point> + 'a {
+    let height = guide_map[point];
+    guide_map
+        .neighbours(point)
+        .filter(move |p| guide_map[p] == height + 1)
+}
+
+fn rating(guide: &TopoMap, pos: &Point) -> usize {
+    if guide[pos] == 9 {
+        return 1;
+    }
+    valid_neighbours(guide, pos)
+        .map(|n| rating(guide, &n))
+        .sum()
+}
+
+fn possible_route(
+    guide: &TopoMap,
+    pos: &Point,
+    peaks_seen_so_far: &mut HashSet<Point>,
+) -> HashSet<Point> {
+    if guide[pos] == 9 {
+        peaks_seen_so_far.insert(*pos);
+        return peaks_seen_so_far.clone();
+    }
+    valid_neighbours(guide, pos)
+        .flat_map(|n| possible_route(guide, &n, peaks_seen_so_far))
+        .collect()
+}
+
+#[aoc(day10, part1)]
+fn part1(input: &TopoMap) -> usize {
+    input
+        .find_all(&0)
+        .map(|p| possible_route(input, &p, &mut HashSet::default()).len())
+        .sum()
+}
+
+#[aoc(day10, part2)]
+fn part2(input: &TopoMap) -> usize {
+    input.find_all(&0).map(|p| rating(input, &p)).sum()
+}
+
+/// Day 10: Hoof It.
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = TopoMap;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 10;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part2(input))
+    }
+}
+
+params! {
+    /// Size of the generated map and how many trails it hides.
+    pub struct Knobs {
+        size: usize = 45,
+        /// Hiking trails from a 0 to a 9 drawn over random heights.
+        trails: usize = 60,
+    }
+}
+
+/// Random heights with trails climbing one step at a time drawn over them, later trails
+/// sometimes cutting through earlier ones.
+impl Generate for Day10 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let size = knobs.size.max(1);
+        let mut map = Grid::new(size, size, b'0');
+        for point in map.points().collect::<Vec<_>>() {
+            map[point] = b'0' + rng.below(10) as u8;
+        }
+        for _ in 0..knobs.trails {
+            let mut point = map.point_of(rng.below(map.len()));
+            let mut trail = Vec::new();
+            for height in b'0'..=b'9' {
+                map[point] = height;
+                trail.push(point);
+                let next = map
+                    .neighbours(&point)
+                    .filter(|p| !trail.contains(p))
+                    .collect::<Vec<_>>();
+                if next.is_empty() {
+                    break;
+                }
+                point = *rng.pick(&next);
+            }
+        }
+        map.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str = r#"0123
+1234
+8765
+9876"#;
+
+    pub const FORK: &str = r#"...0...
+...1...
+...2...
+6543456
+7.....7
+8.....8
+9.....9"#;
+
+    pub const FOUR_SCORE: &str = r#"..90..9
+...1.98
+...2..7
+6543456
+765.987
+876....
+987...."#;
+
+    pub const TWO_TRAILHEADS: &str = r#"10..9..
+2...8..
+3...7..
+4567654
+...8..3
+...9..2
+.....01"#;
+
+    pub const LARGER_EXAMPLE: &str = r#"89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732"#;
+
+    pub const THREE_RATING: &str = r#".....0.
+..4321.
+..5..2.
+..6543.
+..7..4.
+..8765.
+..9...."#;
+
+    pub const THIRTEEN_RATING: &str = r#"..90..9
+...1.98
+...2..7
+6543456
+765.987
+876....
+987...."#;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 1);
+        assert_eq!(part1(&parse(FORK).unwrap()), 2);
+        assert_eq!(part1(&parse(FOUR_SCORE).unwrap()), 4);
+        assert_eq!(part1(&parse(TWO_TRAILHEADS).unwrap()), 3);
+        assert_eq!(part1(&parse(LARGER_EXAMPLE).unwrap()), 36);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(THREE_RATING).unwrap()), 3);
+        assert_eq!(part2(&parse(THIRTEEN_RATING).unwrap()), 13);
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt::Display;
+
+use ahash::{HashMap, HashMapExt};
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::{
+    generate::{Generate, Rng},
+    params::params,
+    parse::{number, ParseError},
+    solution::{Solution, SolveError},
+};
+
+pub type Stones = HashMap<u64, usize>;
+
+#[aoc_generator(day11)]
+fn parse(input: &str) -> Result<Stones, ParseError> {
+    input
+        .split_whitespace()
+        .map(|s| Ok((number(input, s)?, 1)))
+        .collect()
+}
+
+fn apply_rules(input: &Stones) -> Result<Stones, SolveError> {
+    let mut stones = HashMap::with_capacity(input.len());
+    let mut add = |stone: u64, count: usize| {
+        let total: &mut usize = stones.entry(stone).or_default();
+        *total = total
+            .checked_add(count)
+            .ok_or_else(|| SolveError::new("more stones than fit in 64 bits"))?;
+        Ok::<_, SolveError>(())
+    };
+    for (&s, &v) in input {
+        match s {
+            0 => add(1, v)?,
+            _ => {
+                let digits = s.ilog10() + 1;
+                if digits % 2 == 0 {
+                    let pow = 10u64.pow(digits / 2);
+                    add(s % pow, v)?;
+                    add(s / pow, v)?;
+                } else {
+                    let engraved = s.checked_mul(2024).ok_or_else(|| {
+                        SolveError::new(format!("stone {s} times 2024 doesn't fit in 64 bits"))
+                    })?;
+                    add(engraved, v)?;
+                }
+            }
+        }
+    }
+    Ok(stones)
+}
+
+params! {
+    /// How many times the stones blink in each part.
+    pub struct Params {
+        part1_blinks: usize = 25,
+        part2_blinks: usize = 75,
+    }
+}
+
+fn blink(input: &Stones, times: usize) -> Result<usize, SolveError> {
+    let mut stones = input.clone();
+    for _ in 0..times {
+        stones = apply_rules(&stones)?;
+    }
+    (stones.values())
+        .try_fold(0usize, |total, &count| total.checked_add(count))
+        .ok_or_else(|| SolveError::new("more stones than fit in 64 bits"))
+}
+
+fn part1_with(input: &Stones, params: &Params) -> Result<usize, SolveError> {
+    blink(input, params.part1_blinks)
+}
+
+fn part2_with(input: &Stones, params: &Params) -> Result<usize, SolveError> {
+    blink(input, params.part2_blinks)
+}
+
+#[aoc(day11, part1)]
+fn part1(input: &Stones) -> Result<usize, SolveError> {
+    part1_with(input, &Params::default())
+}
+
+#[aoc(day11, part2)]
+fn part2(input: &Stones) -> Result<usize, SolveError> {
+    part2_with(input, &Params::default())
+}
+
+/// Day 11: Plutonian Pebbles.
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Stones;
+    type Params = Params;
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 11;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        part1_with(input, params)
+    }
+
+    fn part2(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        part2_with(input, params)
+    }
+}
+
+params! {
+    /// How many stones to generate, and how big their numbers get.
+    pub struct Knobs {
+        stones: usize = 8,
+        /// The most digits engraved on a stone.
+        digits: u32 = 7,
+    }
+}
+
+/// A row of stones with numbers of up to `digits` digits, zeroes included.
+impl Generate for Day11 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let limit = 10usize.pow(knobs.digits.clamp(1, 18));
+        (0..knobs.stones)
+            .map(|_| rng.below(limit).to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str = "125 17";
+
+    #[test]
+    fn blinks_six_times() {
+        let params = Params {
+            part1_blinks: 6,
+            ..Params::default()
+        };
+        assert_eq!(
+            Day11::part1(&parse(EXAMPLE).unwrap(), &params)
+                .unwrap()
+                .to_string(),
+            "22"
+        );
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), Ok(55312));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), Ok(65601038650482));
+    }
+
+    #[test]
+    fn stones_too_big_to_engrave_are_an_error() {
+        let stones = parse("1 9223372036854775807").unwrap();
+        let error = part1(&stones).unwrap_err();
+        assert_eq!(
+            error.message,
+            "stone 9223372036854775807 times 2024 doesn't fit in 64 bits"
+        );
+    }
+}
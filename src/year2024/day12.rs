@@ -0,0 +1,208 @@
+use std::{collections::VecDeque, fmt::Display};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::{
+    generate::{Generate, Rng},
+    grid::Grid,
+    params::params,
+    parse::ParseError,
+    point::Point,
+    solution::{Solution, SolveError},
+};
+
+#[aoc_generator(day12)]
+fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(input)
+}
+
+/// Flood fills every region, pricing each one as its area times `edge_cost(point)` summed over its tiles.
+fn solve(grid: &Grid<u8>, edge_cost: impl Fn(&Grid<u8>, Point) -> usize) -> usize {
+    let mut visited = grid.same_size_with(false);
+    let mut queue = VecDeque::with_capacity(32);
+    let mut cost = 0;
+    for starting_point in grid.points() {
+        if visited[starting_point] {
+            continue;
+        }
+        let starting_val = grid[starting_point];
+        let mut area = 0;
+        let mut region_edges = 0;
+        queue.push_back(starting_point);
+        visited[starting_point] = true;
+        while let Some(current_point) = queue.pop_front() {
+            area += 1;
+            region_edges += edge_cost(grid, current_point);
+            for neighbour in grid.neighbours(&current_point) {
+                if grid[neighbour] == starting_val && !visited[neighbour] {
+                    visited[neighbour] = true;
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        cost += area * region_edges;
+    }
+    cost
+}
+
+fn get_number_of_fences(grid: &Grid<u8>, point: Point) -> usize {
+    let plant = grid[point];
+    point
+        .cardinal_neighbours()
+        .iter()
+        .filter(|n| grid.get(n) != Some(&plant))
+        .count()
+}
+
+fn get_number_of_corners(grid: &Grid<u8>, point: Point) -> usize {
+    let mut number_of_corners = 0;
+    let mut matches = [[false; 3]; 3];
+    let p = grid.get(&point);
+    for (r_d, row) in matches.iter_mut().enumerate() {
+        for (c_d, matching) in row.iter_mut().enumerate() {
+            let compare_point = point + Point::new(c_d as isize - 1, r_d as isize - 1);
+            *matching = grid.get(&compare_point) == p;
+        }
+    }
+    if matches[0][1] {
+        if matches[1][0] && !matches[0][0] {
+            number_of_corners += 1;
+        }
+        if matches[1][2] && !matches[0][2] {
+            number_of_corners += 1;
+        }
+    } else {
+        if !matches[1][0] {
+            number_of_corners += 1;
+        }
+        if !matches[1][2] {
+            number_of_corners += 1;
+        }
+    }
+    if matches[2][1] {
+        if matches[1][2] && !matches[2][2] {
+            number_of_corners += 1;
+        }
+
+        if matches[1][0] && !matches[2][0] {
+            number_of_corners += 1;
+        }
+    } else {
+        if !matches[1][0] {
+            number_of_corners += 1;
+        }
+
+        if !matches[1][2] {
+            number_of_corners += 1;
+        }
+    }
+    number_of_corners
+}
+
+#[aoc(day12, part1)]
+fn part1(input: &Grid<u8>) -> usize {
+    solve(input, get_number_of_fences)
+}
+
+#[aoc(day12, part2)]
+fn part2(input: &Grid<u8>) -> usize {
+    solve(input, get_number_of_corners)
+}
+
+/// Day 12: Garden Groups.
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Grid<u8>;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 12;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part2(input))
+    }
+}
+
+params! {
+    /// Size of the generated garden and how many regions to start from.
+    pub struct Knobs {
+        size: usize = 140,
+        /// Seeds the regions grow from. Neighbours with the same plant merge.
+        regions: usize = 600,
+    }
+}
+
+/// Regions grown from random seeds, each tile going to the nearest seed with a little noise
+/// added to every distance, which gives ragged edges and the odd enclave.
+impl Generate for Day12 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let size = knobs.size.max(1);
+        let mut garden = Grid::new(size, size, b'A');
+        let seeds: Vec<_> = (0..knobs.regions.max(1))
+            .map(|_| {
+                let seed = garden.point_of(rng.below(garden.len()));
+                (seed, b'A' + rng.below(26) as u8)
+            })
+            .collect();
+        for point in garden.points().collect::<Vec<_>>() {
+            let (_, plant) = seeds
+                .iter()
+                .min_by_key(|(seed, _)| seed.manhattan_distance(&point) * 4 + rng.below(6))
+                .unwrap();
+            garden[point] = *plant;
+        }
+        garden.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const SMALL_EXAMPLE: &str = r#"AAAA
+BBCD
+BBCC
+EEEC"#;
+
+    pub const TIC_TAC_TOE: &str = r#"OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO"#;
+
+    pub const LARGE_EXAMPLE: &str = r#"RRRRIICCFF
+    RRRRIICCCF
+    VVRRRCCFFF
+    VVRCCCJFFF
+    VVVVCJJCFE
+    VVIVCCJJEE
+    VVIIICJJEE
+    MIIIIIJJEE
+    MIIISIJEEE
+    MMMISSJEEE"#;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(SMALL_EXAMPLE).unwrap()), 140);
+        assert_eq!(part1(&parse(TIC_TAC_TOE).unwrap()), 772);
+        assert_eq!(part1(&parse(LARGE_EXAMPLE).unwrap()), 1930);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(SMALL_EXAMPLE).unwrap()), 80);
+        assert_eq!(part2(&parse(TIC_TAC_TOE).unwrap()), 436);
+        assert_eq!(part2(&parse(LARGE_EXAMPLE).unwrap()), 1206);
+    }
+}
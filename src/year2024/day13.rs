@@ -0,0 +1,371 @@
+use std::fmt::Display;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use nom::{
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::map,
+    sequence::{pair, preceded, separated_pair},
+};
+use serde_json::json;
+
+use crate::{
+    combinators::{finish, key_value, sections, signed, then_line, ws, Res},
+    explain::{Explain, Explanation},
+    generate::{Generate, Rng},
+    params::params,
+    parse::{self, ParseError},
+    point::Point,
+    solution::{Solution, SolveError},
+    stream::{Records, Stream},
+};
+
+pub type Machine = (Point, Point, Point);
+
+/// `X<sign><n>, Y<sign><n>`, where `sign` is `+` for buttons and `=` for the prize.
+fn coordinates<'a>(sign: &'static str) -> impl FnMut(&'a str) -> Res<'a, Point> {
+    map(
+        separated_pair(
+            preceded(pair(char('X'), tag(sign)), signed),
+            ws(char(',')),
+            preceded(pair(char('Y'), tag(sign)), signed),
+        ),
+        |(x, y)| Point::new(x, y),
+    )
+}
+
+fn machine(input: &str) -> Res<'_, Machine> {
+    map(
+        then_line(
+            then_line(
+                key_value("Button A", coordinates("+")),
+                key_value("Button B", coordinates("+")),
+            ),
+            key_value("Prize", coordinates("=")),
+        ),
+        |((a, b), prize)| (a, b, prize),
+    )(input)
+}
+
+#[aoc_generator(day13)]
+fn parse(input: &str) -> Result<Vec<Machine>, ParseError> {
+    let machines = finish(input, sections(machine))?;
+    // With both buttons pushing the same way there is no single way to reach the prize, and
+    // the puzzle never asks for the cheapest of many.
+    if let Some(i) = (machines.iter()).position(|(a, b, _)| cross(a, b) == 0) {
+        let section = parse::sections(input)[i];
+        return Err(ParseError::at(
+            input,
+            section.lines().next().unwrap_or(section),
+            "buttons A and B push the claw in the same direction",
+        ));
+    }
+    Ok(machines)
+}
+
+/// `a.x * b.y - a.y * b.x`, which is zero if `a` and `b` point the same way. Products of 64-bit
+/// numbers are at most 2^126 either way, so this always fits in 128 bits.
+fn cross(a: &Point, b: &Point) -> i128 {
+    a.x as i128 * b.y as i128 - a.y as i128 * b.x as i128
+}
+
+fn too_large() -> SolveError {
+    SolveError::new("a claw machine's numbers are too large to solve")
+}
+
+/// How many times to press A and B to land exactly on the prize moved by `offset`, if that's
+/// possible at all.
+fn presses(
+    (a_button, b_button, prize): &Machine,
+    offset: isize,
+) -> Result<Option<(i128, i128)>, SolveError> {
+    let offset = offset as i128;
+    let target = Point::new(prize.x as i128 + offset, prize.y as i128 + offset);
+    let det = cross(a_button, b_button);
+    let a_times = (target.x.checked_mul(b_button.y as i128))
+        .zip(target.y.checked_mul(b_button.x as i128))
+        .and_then(|(x, y)| x.checked_sub(y))
+        .ok_or_else(too_large)?;
+    let b_times = (target.y.checked_mul(a_button.x as i128))
+        .zip(target.x.checked_mul(a_button.y as i128))
+        .and_then(|(y, x)| y.checked_sub(x))
+        .ok_or_else(too_large)?;
+    let (Some(a), Some(b)) = (a_times.checked_div(det), b_times.checked_div(det)) else {
+        return Ok(None);
+    };
+    // Sums too large for 128 bits can't be the prize, whose coordinates are far smaller.
+    let lands = |a_step: isize, b_step: isize, at: i128| {
+        (a.checked_mul(a_step as i128))
+            .zip(b.checked_mul(b_step as i128))
+            .and_then(|(a, b)| a.checked_add(b))
+            == Some(at)
+    };
+    let won = a >= 0
+        && b >= 0
+        && lands(a_button.x, b_button.x, target.x)
+        && lands(a_button.y, b_button.y, target.y);
+    Ok(won.then_some((a, b)))
+}
+
+/// The tokens it takes to win the prize moved by `offset`: three per press of A, one per B.
+fn tokens(machine: &Machine, offset: isize) -> Result<Option<i128>, SolveError> {
+    let Some((a, b)) = presses(machine, offset)? else {
+        return Ok(None);
+    };
+    let tokens = a.checked_mul(3).and_then(|a| a.checked_add(b));
+    tokens.ok_or_else(too_large).map(Some)
+}
+
+/// The tokens for every prize that can be won, with each prize moved by `offset`.
+fn total(input: &[Machine], offset: isize) -> Result<i128, SolveError> {
+    input.iter().try_fold(0i128, |total, machine| {
+        let tokens = tokens(machine, offset)?.unwrap_or(0);
+        total.checked_add(tokens).ok_or_else(too_large)
+    })
+}
+
+#[aoc(day13, part1)]
+fn part1(input: &[Machine]) -> Result<i128, SolveError> {
+    total(input, 0)
+}
+
+params! {
+    /// How far part 2 moves every prize along both axes.
+    pub struct Params {
+        part2_offset: isize = 10000000000000,
+    }
+}
+
+#[aoc(day13, part2)]
+fn part2(input: &[Machine]) -> Result<i128, SolveError> {
+    total(input, Params::default().part2_offset)
+}
+
+/// Day 13: Claw Contraption.
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Machine>;
+    type Params = Params;
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 13;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        total(input, params.part2_offset)
+    }
+}
+
+/// Adds up the tokens one machine at a time. Each part keeps its running total, or the error
+/// from the first machine too large to solve.
+impl Stream for Day13 {
+    type State = [(i128, Option<SolveError>); 2];
+
+    const RECORDS: Records = Records::Sections;
+
+    fn fold(
+        parts: &mut [(i128, Option<SolveError>); 2],
+        record: &str,
+        params: &Params,
+    ) -> Result<(), ParseError> {
+        let machines = parse(record)?;
+        for ((tokens, error), offset) in parts.iter_mut().zip([0, params.part2_offset]) {
+            if error.is_some() {
+                continue;
+            }
+            match total(&machines, offset)
+                .and_then(|more| tokens.checked_add(more).ok_or_else(too_large))
+            {
+                Ok(sum) => *tokens = sum,
+                Err(e) => *error = Some(e),
+            }
+        }
+        Ok(())
+    }
+
+    fn answer(
+        parts: &[(i128, Option<SolveError>); 2],
+        _: &Params,
+        part: u8,
+    ) -> Result<String, SolveError> {
+        match &parts[part as usize - 1] {
+            (_, Some(error)) => Err(error.clone()),
+            (tokens, None) => Ok(tokens.to_string()),
+        }
+    }
+}
+
+/// Shows the presses and tokens for every prize that can be won.
+impl Explain for Day13 {
+    fn explain(input: &Vec<Machine>, params: &Params, part: u8) -> Explanation {
+        // Counts as large as these only fit in JSON as text.
+        let number = |n: i128| i64::try_from(n).map_or_else(|_| json!(n.to_string()), |n| json!(n));
+        let offset = if part == 1 { 0 } else { params.part2_offset };
+        let mut spent = Some(0i128);
+        let mut won = 0;
+        let witness = input
+            .iter()
+            .map(|machine| {
+                let prize = [
+                    number(machine.2.x as i128 + offset as i128),
+                    number(machine.2.y as i128 + offset as i128),
+                ];
+                let found = presses(machine, offset)
+                    .and_then(|found| Ok(found.zip(tokens(machine, offset)?)));
+                match found {
+                    Ok(Some(((a, b), cost))) => {
+                        spent = spent.and_then(|spent| spent.checked_add(cost));
+                        won += 1;
+                        let (a, b, cost) = (number(a), number(b), number(cost));
+                        json!({"prize": prize, "a": a, "b": b, "tokens": cost})
+                    }
+                    Ok(None) => json!({"prize": prize, "won": false}),
+                    Err(e) => {
+                        spent = None;
+                        json!({"prize": prize, "error": e.message})
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        Explanation::new(
+            spent.map_or("none".to_string(), |spent| spent.to_string()),
+            json!({"won": won, "of": input.len(), "machines": witness}),
+        )
+    }
+}
+
+params! {
+    /// How many claw machines to generate.
+    pub struct Knobs {
+        machines: usize = 320,
+        /// Chance that the prize sits where some number of presses will reach it.
+        winnable: f64 = 0.5,
+    }
+}
+
+/// Machines with buttons that move 10 to 99 along each axis. Winnable prizes are up to a
+/// hundred presses of each button away, the others anywhere in the same range.
+impl Generate for Day13 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let mut machines = Vec::new();
+        while machines.len() < knobs.machines {
+            let a = Point::new(rng.between(10, 99), rng.between(10, 99));
+            let b = Point::new(rng.between(10, 99), rng.between(10, 99));
+            // The puzzle never has buttons that point the same way.
+            if a.x * b.y == a.y * b.x {
+                continue;
+            }
+            let prize = if rng.chance(knobs.winnable) {
+                a * rng.between(0, 100) + b * rng.between(0, 100)
+            } else {
+                Point::new(rng.between(0, 19800), rng.between(0, 19800))
+            };
+            machines.push(format!(
+                "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}",
+                a.x, a.y, b.x, b.y, prize.x, prize.y
+            ));
+        }
+        machines.join("\n\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str = r#"Button A: X+94, Y+34
+    Button B: X+22, Y+67
+    Prize: X=8400, Y=5400
+
+    Button A: X+26, Y+66
+    Button B: X+67, Y+21
+    Prize: X=12748, Y=12176
+
+    Button A: X+17, Y+86
+    Button B: X+84, Y+37
+    Prize: X=7870, Y=6450
+
+    Button A: X+69, Y+23
+    Button B: X+27, Y+71
+    Prize: X=18641, Y=10279"#;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), Ok(480));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), Ok(875318608908));
+    }
+
+    #[test]
+    fn explains_the_presses() {
+        let explanation = Day13::explain(&parse(EXAMPLE).unwrap(), &Params::default(), 1);
+        assert_eq!(explanation.answer, "480");
+        let first = &explanation.witness["machines"][0];
+        assert_eq!(
+            (&first["a"], &first["b"], &first["tokens"]),
+            (&80.into(), &40.into(), &280.into())
+        );
+        assert_eq!(explanation.witness["machines"][1]["won"], false);
+    }
+
+    #[test]
+    fn rejects_buttons_pushing_the_same_way() {
+        let machine = "Button A: X+1, Y+2\nButton B: X+3, Y+4\nPrize: X=5, Y=6";
+        let input =
+            format!("{machine}\n\nButton A: X+2, Y+4\nButton B: X+3, Y+6\nPrize: X=5, Y=10");
+        let error = parse(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (5, 1));
+    }
+
+    #[test]
+    fn needs_presses_that_can_be_made() {
+        // A doesn't move the claw along X, which the solver used to divide by.
+        assert_eq!(
+            presses(&parse_one("X+0, Y+1", "X+1, Y+1", "X=3, Y=5"), 0),
+            Ok(Some((2, 3)))
+        );
+        // Reaching this prize would take -1 presses of B.
+        assert_eq!(
+            presses(&parse_one("X+2, Y+1", "X+1, Y+1", "X=3, Y=1"), 0),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn huge_buttons_and_prizes_do_not_overflow() {
+        let max = isize::MAX;
+        let machine = parse_one(
+            &format!("X+{max}, Y+1"),
+            "X+1, Y+2",
+            &format!("X={max}, Y=1"),
+        );
+        assert_eq!(presses(&machine, 0), Ok(Some((1, 0))));
+        assert_eq!(total(&[machine], max), Ok(0));
+        let input = format!("Button A: X+1, Y+0\nButton B: X+1, Y+{max}\nPrize: X={max}, Y=0");
+        let machines = parse(&input).unwrap();
+        assert_eq!(part1(&machines), Ok(3 * max as i128));
+        let far = parse_one(
+            "X+1, Y+0",
+            &format!("X+{}, Y+{max}", isize::MIN),
+            &format!("X={max}, Y={max}"),
+        );
+        assert_eq!(total(&[far], max), Err(too_large()));
+    }
+
+    fn parse_one(a: &str, b: &str, prize: &str) -> Machine {
+        parse(&format!("Button A: {a}\nButton B: {b}\nPrize: {prize}")).unwrap()[0]
+    }
+}
@@ -0,0 +1,554 @@
+use std::{fmt::Display, io, iter::successors, num::NonZero};
+
+use ahash::HashSet;
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, space1},
+    combinator::map,
+    sequence::{pair, preceded, separated_pair},
+};
+
+use crate::{
+    combinators::{finish, lines, signed, Res},
+    generate::{Generate, Rng},
+    grid::Grid,
+    params::params,
+    parse::ParseError,
+    point::Point,
+    solution::{Solution, SolveError, Variant},
+    stream::Stream,
+    visualize::{Animate, Frame, Sink, ACTOR},
+};
+
+#[derive(Debug, Clone)]
+pub struct Robot {
+    pos: Point,
+    vel: Point,
+}
+
+impl Robot {
+    fn tick(&self, ticks: isize, width: usize, height: usize) -> Robot {
+        Robot {
+            pos: Point::new(
+                wrap(self.pos.x, self.vel.x, ticks, width as isize),
+                wrap(self.pos.y, self.vel.y, ticks, height as isize),
+            ),
+            vel: self.vel,
+        }
+    }
+}
+
+/// Where a robot at `pos` moving `vel` a second is after `steps` seconds, along an axis `size`
+/// tiles long. Working modulo `size` in 128 bits keeps any velocity from overflowing.
+fn wrap(pos: isize, vel: isize, steps: isize, size: isize) -> isize {
+    let size = size as i128;
+    let moved = (vel as i128).rem_euclid(size) * (steps as i128).rem_euclid(size);
+    ((pos as i128).rem_euclid(size) + moved).rem_euclid(size) as isize
+}
+
+/// The product of the robots in each quadrant.
+fn safety(quadrants: &Quadrants) -> Result<usize, SolveError> {
+    let (a, b, c, d) = *quadrants;
+    (a.checked_mul(b))
+        .and_then(|ab| ab.checked_mul(c))
+        .and_then(|abc| abc.checked_mul(d))
+        .ok_or_else(|| SolveError::new("the safety factor doesn't fit in 64 bits"))
+}
+
+pub type Quadrants = (usize, usize, usize, usize);
+
+fn vector<'a>(name: &'static str) -> impl FnMut(&'a str) -> Res<'a, Point> {
+    preceded(
+        pair(tag(name), char('=')),
+        map(separated_pair(signed, char(','), signed), |(x, y)| {
+            Point::new(x, y)
+        }),
+    )
+}
+
+fn robot(input: &str) -> Res<'_, Robot> {
+    map(
+        separated_pair(vector("p"), space1, vector("v")),
+        |(pos, vel)| Robot { pos, vel },
+    )(input)
+}
+
+#[aoc_generator(day14)]
+fn parse(input: &str) -> Result<Vec<Robot>, ParseError> {
+    finish(input, lines(robot))
+}
+
+pub const GRID_HEIGHT: usize = 103;
+pub const GRID_WIDTH: usize = 101;
+
+params! {
+    /// The size of the room, and how long part 1 watches the robots for. The room is at least
+    /// one tile and at most 65535 tiles either way, so its area always fits.
+    pub struct Params {
+        width: NonZero<u16> = side(GRID_WIDTH),
+        height: NonZero<u16> = side(GRID_HEIGHT),
+        seconds: isize = 100,
+    }
+}
+
+fn side(tiles: usize) -> NonZero<u16> {
+    NonZero::new(tiles as u16).expect("the room has tiles")
+}
+
+impl Params {
+    /// The width and height of the room.
+    fn room(&self) -> (usize, usize) {
+        (self.width.get().into(), self.height.get().into())
+    }
+}
+
+fn safety_factor(robots: &[Robot], params: &Params) -> Quadrants {
+    let (width, height) = params.room();
+    let quadrant_width = width / 2;
+    let quadrant_height = height / 2;
+    robots.iter().fold((0, 0, 0, 0), |quadrants, robot| {
+        let end = robot.tick(params.seconds, width, height);
+        match end.pos.x.cmp(&(quadrant_width as isize)) {
+            std::cmp::Ordering::Less => match end.pos.y.cmp(&(quadrant_height as isize)) {
+                std::cmp::Ordering::Less => {
+                    (quadrants.0 + 1, quadrants.1, quadrants.2, quadrants.3)
+                }
+                std::cmp::Ordering::Greater => {
+                    (quadrants.0, quadrants.1 + 1, quadrants.2, quadrants.3)
+                }
+                _ => quadrants,
+            },
+            std::cmp::Ordering::Greater => match end.pos.y.cmp(&(quadrant_height as isize)) {
+                std::cmp::Ordering::Less => {
+                    (quadrants.0, quadrants.1, quadrants.2 + 1, quadrants.3)
+                }
+                std::cmp::Ordering::Greater => {
+                    (quadrants.0, quadrants.1, quadrants.2, quadrants.3 + 1)
+                }
+                _ => quadrants,
+            },
+            _ => quadrants,
+        }
+    })
+}
+
+fn part1_with(input: &[Robot], params: &Params) -> Result<usize, SolveError> {
+    safety(&safety_factor(input, params))
+}
+
+#[aoc(day14, part1)]
+fn part1(input: &[Robot]) -> Result<usize, SolveError> {
+    part1_with(input, &Params::default())
+}
+
+fn calculate_variance(positions: &[Point]) -> f64 {
+    let mean = positions.iter().map(|&p| p.x as f64).sum::<f64>() / positions.len() as f64;
+    let variance = positions
+        .iter()
+        .map(|&p| {
+            let diff = p.x as f64 - mean;
+            diff * diff
+        })
+        .sum::<f64>()
+        / positions.len() as f64;
+    variance
+}
+
+fn find_best_offset(robots: &[Robot], modulo: usize, x_axis: bool) -> usize {
+    let mut best_variance = f64::MAX;
+    let mut best_offset = 0;
+    for offset in 0..modulo {
+        let positions: Vec<_> =
+            move_robots(robots, modulo as isize, modulo as isize, offset as isize).collect();
+        let variance = if x_axis {
+            calculate_variance(&positions)
+        } else {
+            calculate_variance(
+                &positions
+                    .iter()
+                    .map(|p| Point { x: p.y, y: p.x })
+                    .collect_vec(),
+            )
+        };
+        if variance < best_variance {
+            best_variance = variance;
+            best_offset = offset;
+        }
+    }
+    best_offset
+}
+
+fn move_robots<'a>(
+    robots: &'a [Robot],
+    width: isize,
+    height: isize,
+    steps: isize,
+) -> impl Iterator<Item = Point> + 'a {
+    robots.iter().map(move |robot| Point {
+        x: wrap(robot.pos.x, robot.vel.x, steps, width),
+        y: wrap(robot.pos.y, robot.vel.y, steps, height),
+    })
+}
+
+fn part2_with(robots: &[Robot], params: &Params) -> Result<usize, SolveError> {
+    let (width, height) = params.room();
+    successors(Some(robots.to_vec()), |robots| {
+        let new_robots = robots
+            .iter()
+            .map(|r| r.tick(1, width, height))
+            .collect_vec();
+        Some(new_robots)
+    })
+    // Every robot is back where it started after `width * height` seconds.
+    .take(width * height)
+    .enumerate()
+    .find(|(_i, new_robots)| {
+        new_robots.clone().len() == HashSet::from_iter(new_robots.iter().map(|r| r.pos)).len()
+    })
+    .map(|(i, _)| i)
+    .ok_or_else(|| SolveError::new("the robots never all stand on different tiles"))
+}
+
+#[aoc(day14, part2)]
+fn part2(robots: &[Robot]) -> Result<usize, SolveError> {
+    part2_with(robots, &Params::default())
+}
+
+/// The robots line up in columns every `width` ticks and in rows every `height` ticks, so
+/// find the least spread out offset along each axis and combine the two.
+fn part2_variance_with(robots: &[Robot], params: &Params) -> Result<usize, SolveError> {
+    let (width, height) = params.room();
+    let x = find_best_offset(robots, width, true);
+    let y = find_best_offset(robots, height, false);
+    (x..width * height)
+        .step_by(width)
+        .find(|t| t % height == y)
+        .ok_or_else(|| SolveError::new("the tightest columns and rows never line up"))
+}
+
+#[aoc(day14, part2, VARIANCE)]
+fn part2_variance(robots: &[Robot]) -> Result<usize, SolveError> {
+    part2_variance_with(robots, &Params::default())
+}
+
+/// Day 14: Restroom Redoubt.
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Robot>;
+    type Params = Params;
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 14;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        part1_with(input, params)
+    }
+
+    fn part2(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        part2_with(input, params)
+    }
+
+    fn variants() -> Vec<Variant<Self>> {
+        vec![Variant {
+            part: 2,
+            name: "VARIANCE",
+            solve: |input, params| Ok(part2_variance_with(input, params)?.to_string()),
+        }]
+    }
+}
+
+/// Counts where each robot ends up for part 1. Finding the tree needs every robot at once.
+impl Stream for Day14 {
+    type State = Quadrants;
+
+    const PARTS: &'static [u8] = &[1];
+
+    fn fold(quadrants: &mut Quadrants, record: &str, params: &Params) -> Result<(), ParseError> {
+        let (a, b, c, d) = safety_factor(&parse(record)?, params);
+        *quadrants = (
+            quadrants.0 + a,
+            quadrants.1 + b,
+            quadrants.2 + c,
+            quadrants.3 + d,
+        );
+        Ok(())
+    }
+
+    fn answer(quadrants: &Quadrants, _: &Params, _: u8) -> Result<String, SolveError> {
+        Ok(safety(quadrants)?.to_string())
+    }
+}
+
+/// Shows the room once a second, up to whichever comes last of the second part 1 asks about
+/// and the one where the robots draw the Christmas tree, if they ever do.
+impl Animate for Day14 {
+    fn animate(robots: &Vec<Robot>, params: &Params, sink: &mut dyn Sink) -> io::Result<()> {
+        let (width, height) = params.room();
+        let tree = part2_variance_with(robots, params).ok();
+        let last = params.seconds.max(0) as usize;
+        for second in 0..=last.max(tree.unwrap_or(0)) {
+            let mut frame = Frame::new(width, height);
+            for position in move_robots(robots, width as isize, height as isize, second as isize) {
+                frame.draw(position, '#', ACTOR);
+            }
+            frame.caption = if Some(second) == tree {
+                format!("second {second}, the Christmas tree")
+            } else {
+                format!("second {second}")
+            };
+            sink.frame(&frame)?;
+        }
+        Ok(())
+    }
+}
+
+params! {
+    /// How many robots to generate, and the room they're in.
+    pub struct Knobs {
+        robots: usize = 500,
+        width: usize = GRID_WIDTH,
+        height: usize = GRID_HEIGHT,
+    }
+}
+
+impl Knobs {
+    /// The room asked for, made to fit the limits of [`Params`].
+    fn room(&self) -> (usize, usize) {
+        let fit = |tiles: usize| tiles.clamp(1, u16::MAX.into());
+        (fit(self.width), fit(self.height))
+    }
+}
+
+/// Robots that all stand on different tiles at one random second, most of them filling in a
+/// triangular tree near the middle of the room and the rest scattered around it. Everything is
+/// then wound back to second zero.
+impl Generate for Day14 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let (width, height) = knobs.room();
+        let robots = knobs.robots.min(width * height);
+        let mut picture = Grid::new(width, height, false);
+        let rows = ((robots as f64 * 0.6).sqrt() as usize)
+            .min(height)
+            .min(width.div_ceil(2));
+        if rows > 0 {
+            // Somewhere in the middle half of the room, like the real tree.
+            let (free_x, free_y) = ((width + 1 - 2 * rows) / 4, (height - rows) / 4);
+            let top = Point::new(
+                (width / 2) as isize + rng.between(-(free_x as isize), free_x as isize),
+                ((height - rows) / 2) as isize + rng.between(-(free_y as isize), free_y as isize),
+            );
+            for row in 0..rows as isize {
+                for x in -row..=row {
+                    picture[Point::new(top.x + x, top.y + row)] = true;
+                }
+            }
+        }
+        let mut tiles = picture.find_all(&true).collect_vec();
+        let mut elsewhere = picture.find_all(&false).collect_vec();
+        rng.shuffle(&mut elsewhere);
+        tiles.extend(
+            elsewhere
+                .into_iter()
+                .take(robots.saturating_sub(tiles.len())),
+        );
+        // Part 2 takes the first second with every robot apart and VARIANCE the second they
+        // bunch up tightest, so draw again until both land on the tree. A handful of robots
+        // rarely manages that, so after a while they're left standing still instead, which
+        // both read as second zero.
+        let params = Params {
+            width: side(width),
+            height: side(height),
+            ..Params::default()
+        };
+        let robots = (0..100)
+            .find_map(|_| {
+                let second = rng.below(width * height);
+                let robots = tiles
+                    .iter()
+                    .map(|tile| {
+                        let vel = Point::new(
+                            rng.between(-(width as isize) + 1, width as isize - 1),
+                            rng.between(-(height as isize) + 1, height as isize - 1),
+                        );
+                        let pos =
+                            (tile - vel * second as isize).bounded(width as isize, height as isize);
+                        Robot { pos, vel }
+                    })
+                    .collect_vec();
+                let mut seen = Grid::new(width, height, usize::MAX);
+                let apart_earlier = (0..second).any(|t| {
+                    move_robots(&robots, width as isize, height as isize, t as isize)
+                        .all(|p| std::mem::replace(&mut seen[p], t) != t)
+                });
+                let tightest = part2_variance_with(&robots, &params);
+                (!apart_earlier && tightest == Ok(second)).then_some(robots)
+            })
+            .unwrap_or_else(|| {
+                tiles
+                    .iter()
+                    .map(|&pos| Robot {
+                        pos,
+                        vel: Point::new(0, 0),
+                    })
+                    .collect()
+            });
+        robots
+            .iter()
+            .map(|r| format!("p={},{} v={},{}", r.pos.x, r.pos.y, r.vel.x, r.vel.y))
+            .join("\n")
+    }
+
+    fn params_for(knobs: &Knobs) -> Vec<(String, String)> {
+        vec![
+            ("width".to_string(), knobs.room().0.to_string()),
+            ("height".to_string(), knobs.room().1.to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    pub const EXAMPLE: &str = r#"p=0,4 v=3,-3
+    p=6,3 v=-1,-3
+    p=10,3 v=-1,2
+    p=2,0 v=2,-1
+    p=0,0 v=1,3
+    p=3,0 v=-2,-2
+    p=7,6 v=-1,-3
+    p=3,0 v=-1,-2
+    p=9,3 v=2,3
+    p=7,3 v=-1,2
+    p=2,4 v=2,-3
+    p=9,5 v=-3,-3"#;
+
+    #[test]
+    fn part1_example() {
+        let params = Params {
+            width: side(11),
+            height: side(7),
+            ..Params::default()
+        };
+        let robots = parse(EXAMPLE).unwrap();
+        assert_eq!(safety_factor(&robots, &params), (1, 4, 3, 1));
+        assert_eq!(Day14::part1(&robots, &params).unwrap().to_string(), "12");
+    }
+
+    #[test]
+    fn fast_robots_do_not_overflow() {
+        let robots = parse("p=-5,-5 v=-9223372036854775807,3").unwrap();
+        assert_eq!(part1(&robots), Ok(0));
+        let end = robots[0].tick(100, GRID_WIDTH, GRID_HEIGHT);
+        assert_eq!(end.pos, Point::new(84, 89));
+        let far = move_robots(&robots, 101, 103, isize::MAX).next();
+        assert_eq!(far, Some(Point::new(53, 23)));
+    }
+
+    #[test]
+    fn rejects_rooms_without_tiles() {
+        let room = |size: [(&'static str, &'static str); 2]| {
+            <Params as crate::params::Params>::with(size).map(|params| params.room())
+        };
+        assert!(room([("width", "0"), ("height", "7")]).is_err());
+        assert!(room([("width", "11"), ("height", "65536")]).is_err());
+        assert_eq!(
+            room([("width", "11"), ("height", "65535")]),
+            Ok((11, 65535))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), Ok(1));
+        // Two robots moving as one never leave each other's tile.
+        assert!(part2(&parse("p=1,2 v=3,4\np=1,2 v=3,4").unwrap()).is_err());
+    }
+
+    #[test]
+    fn variance_agrees_with_part2() {
+        // A 25x20 block of robots that only comes together at tick 1234.
+        let mut seed = 7u64;
+        let mut random = |n: isize| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as isize % n - n / 2
+        };
+        let robots = (0..25)
+            .cartesian_product(0..20)
+            .map(|(x, y)| {
+                let vel = Point::new(random(200), random(200));
+                let pos = (Point::new(40 + x, 40 + y) + vel * -1234)
+                    .bounded(GRID_WIDTH as isize, GRID_HEIGHT as isize);
+                Robot { pos, vel }
+            })
+            .collect_vec();
+        assert_eq!(part2_variance(&robots), Ok(1234));
+        assert_eq!(part2(&robots), Ok(1234));
+    }
+
+    #[test]
+    fn generated_trees_are_the_tightest_second() {
+        // A small tree among this many robots used to lose to a tighter spread of stragglers.
+        let knobs = [("robots", "150"), ("width", "31"), ("height", "29")]
+            .map(|(k, v)| (k.to_string(), v.to_string()));
+        let generated = crate::generate::generator(2024, 14)
+            .unwrap()
+            .run(12101093409131538329, &knobs)
+            .unwrap();
+        let robots = parse(&generated.input).unwrap();
+        let params = Params {
+            width: side(31),
+            height: side(29),
+            ..Params::default()
+        };
+        let tree = part2_with(&robots, &params).unwrap();
+        assert_eq!(part2_variance_with(&robots, &params), Ok(tree));
+    }
+
+    proptest! {
+        #[test]
+        fn tick_agrees_with_move_robots(
+            (width, height) in (1usize..120, 1usize..120),
+            (x, y) in (-200isize..200, -200isize..200),
+            (vx, vy) in (-100isize..=100, -100isize..=100),
+            ticks in -100_000isize..100_000,
+        ) {
+            let robot = Robot { pos: Point::new(x, y), vel: Point::new(vx, vy) };
+            let moved = move_robots(std::slice::from_ref(&robot), width as isize, height as isize, ticks)
+                .next()
+                .unwrap();
+            prop_assert_eq!(robot.tick(ticks, width, height).pos, moved);
+        }
+    }
+
+    #[test]
+    fn animates_every_second() {
+        let params = Params {
+            width: side(11),
+            height: side(7),
+            ..Params::default()
+        };
+        let mut frames = Vec::new();
+        Day14::animate(&parse(EXAMPLE).unwrap(), &params, &mut frames).unwrap();
+        assert_eq!(frames.len(), 101);
+        let occupied = frames[100]
+            .cells
+            .values()
+            .filter(|c| c.glyph == '#')
+            .count();
+        assert_eq!(occupied, 10);
+    }
+}
@@ -0,0 +1,288 @@
+use std::{collections::VecDeque, fmt::Display, io};
+
+use ahash::HashSet;
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+
+use crate::{
+    generate::{Generate, Rng},
+    grid::{self, Grid},
+    params::params,
+    parse::{self, ParseError},
+    point::Point,
+    solution::{Solution, SolveError},
+    visualize::{Animate, Cell, Frame, Sink, ACTOR, FLOOR, OBJECT, WALL},
+};
+
+#[aoc_generator(day15)]
+pub fn parse(input: &str) -> Result<(Grid<u8>, String), ParseError> {
+    let [warehouse, insts] = parse::exact_sections(input)?;
+    let grid = Grid::parse_only(warehouse, b"#.O@").map_err(|e| e.rebase(input, warehouse))?;
+    grid.require(warehouse, b'@')
+        .map_err(|e| e.rebase(input, warehouse))?;
+    // The robot and boxes never leave the warehouse, as long as walls keep them in.
+    let edge = |p: &Point| {
+        p.x == 0 || p.y == 0 || p.x == grid.width as isize - 1 || p.y == grid.height as isize - 1
+    };
+    if let Some(gap) = grid.points().find(|p| edge(p) && grid[p] != b'#') {
+        return Err(
+            grid::error_at(warehouse, gap, "expected walls all around the warehouse")
+                .rebase(input, warehouse),
+        );
+    }
+    if let Some((i, c)) = insts
+        .char_indices()
+        .find(|&(_, c)| !"^>v<".contains(c) && !c.is_whitespace())
+    {
+        return Err(ParseError::at(
+            input,
+            &insts[i..i + c.len_utf8()],
+            "expected a move, one of `^>v<`",
+        ));
+    }
+    Ok((grid, insts.to_string()))
+}
+
+fn direction(instruction: u8) -> Option<Point> {
+    match instruction {
+        b'^' => Some(Point::new(0, -1)),
+        b'>' => Some(Point::new(1, 0)),
+        b'v' => Some(Point::new(0, 1)),
+        b'<' => Some(Point::new(-1, 0)),
+        _ => None,
+    }
+}
+
+/// Moves the robot one step along `delta`, pushing any boxes in the way, and returns where it
+/// ends up. Nothing moves if a wall blocks the robot or any of the boxes.
+fn push(g: &mut Grid<u8>, robot: Point, delta: Point) -> Point {
+    let mut q = VecDeque::from([robot]);
+    let mut seen = HashSet::default();
+    while let Some(p) = q.pop_front() {
+        if !seen.insert(p) {
+            continue;
+        }
+        let next = p + delta;
+        match g[next] {
+            b'#' => return robot,
+            b'O' => q.push_back(next),
+            b'[' => q.extend([next, next + Point::new(1, 0)]),
+            b']' => q.extend([next, next + Point::new(-1, 0)]),
+            _ => continue,
+        }
+    }
+    let boxes = seen
+        .iter()
+        .sorted_by_key(|p| (robot.x.abs_diff(p.x), robot.y.abs_diff(p.y)))
+        .rev();
+    for p in boxes {
+        g[p + delta] = g[p];
+        g[p] = b'.';
+    }
+    robot + delta
+}
+
+fn solve(mut g: Grid<u8>, insts: &str) -> usize {
+    let mut robot = g.find(&b'@').unwrap();
+    for delta in insts.bytes().filter_map(direction) {
+        robot = push(&mut g, robot, delta);
+    }
+    g.iter()
+        .filter(|(_, v)| matches!(v, b'O' | b'['))
+        .map(|(p, _)| p.y as usize * 100 + p.x as usize)
+        .sum()
+}
+
+#[aoc(day15, part1)]
+fn part1((grid, instructions): &(Grid<u8>, String)) -> usize {
+    solve(grid.clone(), instructions)
+}
+
+/// The warehouse from part 2, with everything but the robot twice as wide.
+fn widen(grid: &Grid<u8>) -> Grid<u8> {
+    let wide = grid
+        .values()
+        .flat_map(|b| match b {
+            b'#' => b"##",
+            b'O' => b"[]",
+            b'.' => b"..",
+            b'@' => b"@.",
+            _ => unreachable!(),
+        })
+        .copied()
+        .collect();
+    Grid::from_vec(wide, grid.width * 2)
+}
+
+#[aoc(day15, part2)]
+fn part2((grid, instructions): &(Grid<u8>, String)) -> usize {
+    solve(widen(grid), instructions)
+}
+
+/// Day 15: Warehouse Woes.
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = (Grid<u8>, String);
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 15;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part2(input))
+    }
+}
+
+/// Plays the robot's moves in the wide warehouse from part 2.
+impl Animate for Day15 {
+    fn animate(
+        (grid, instructions): &(Grid<u8>, String),
+        _: &(),
+        sink: &mut dyn Sink,
+    ) -> io::Result<()> {
+        let draw = |warehouse: &Grid<u8>, caption: String| {
+            Frame::from_grid(warehouse, |_, &b| match b {
+                b'#' => Cell::new('#', WALL),
+                b'[' => Cell::new('[', OBJECT),
+                b']' => Cell::new(']', OBJECT),
+                b'@' => Cell::new('@', ACTOR),
+                _ => Cell::new('.', FLOOR),
+            })
+            .with_caption(caption)
+        };
+        let mut warehouse = widen(grid);
+        let mut robot = warehouse.find(&b'@').unwrap();
+        sink.frame(&draw(&warehouse, "start".to_string()))?;
+        let moves = instructions.bytes().filter(|&b| direction(b).is_some());
+        for (step, instruction) in moves.enumerate() {
+            robot = push(&mut warehouse, robot, direction(instruction).unwrap());
+            let caption = format!("move {}: {}", step + 1, instruction as char);
+            sink.frame(&draw(&warehouse, caption))?;
+        }
+        Ok(())
+    }
+}
+
+params! {
+    /// Size of the generated warehouse, what's in it and how many moves the robot makes.
+    pub struct Knobs {
+        size: usize = 50,
+        /// Chance that a tile inside the walls holds a box, or a wall.
+        boxes: f64 = 0.3,
+        walls: f64 = 0.06,
+        moves: usize = 20000,
+    }
+}
+
+/// A walled warehouse strewn with boxes and bits of wall, and a list of random moves in lines
+/// of a thousand like the real thing.
+impl Generate for Day15 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let size = knobs.size.max(3);
+        let mut warehouse = Grid::new(size, size, b'#');
+        for point in warehouse.points().collect_vec() {
+            let inside = point.x > 0
+                && point.y > 0
+                && point.x < size as isize - 1
+                && point.y < size as isize - 1;
+            if inside {
+                warehouse[point] = if rng.chance(knobs.walls) {
+                    b'#'
+                } else if rng.chance(knobs.boxes) {
+                    b'O'
+                } else {
+                    b'.'
+                };
+            }
+        }
+        let robot = Point::new(
+            rng.between(1, size as isize - 2),
+            rng.between(1, size as isize - 2),
+        );
+        warehouse[robot] = b'@';
+        let moves = (0..knobs.moves)
+            .map(|_| *rng.pick(&['^', '>', 'v', '<']))
+            .chunks(1000)
+            .into_iter()
+            .map(|line| line.collect::<String>())
+            .join("\n");
+        format!("{warehouse}\n\n{moves}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const SMALL_EXAMPLE: &str = r#"########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<"#;
+
+    pub const LARGE_EXAMPLE: &str = r#"##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"#;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(SMALL_EXAMPLE).unwrap()), 2028);
+        assert_eq!(part1(&parse(LARGE_EXAMPLE).unwrap()), 10092);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(LARGE_EXAMPLE).unwrap()), 9021);
+    }
+
+    #[test]
+    fn rejects_warehouses_without_walls() {
+        let error = parse("####\n#.@.\n####\n\n>>").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.message, "expected walls all around the warehouse");
+    }
+
+    #[test]
+    fn animates_the_wide_warehouse() {
+        let mut frames = Vec::new();
+        Day15::animate(&parse(LARGE_EXAMPLE).unwrap(), &(), &mut frames).unwrap();
+        assert_eq!(frames.len(), 701);
+        assert_eq!(frames[0].cells.width, 20);
+        let robot = frames[700].cells.find(&Cell::new('@', ACTOR)).unwrap();
+        assert_eq!(robot, Point::new(4, 7));
+    }
+}
@@ -0,0 +1,297 @@
+use std::{fmt::Display, io};
+
+use ahash::HashSet;
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+use serde_json::json;
+
+use crate::{
+    explain::{Explain, Explanation},
+    generate::{maze, Generate, Rng},
+    grid::{self, Grid},
+    params::params,
+    parse::ParseError,
+    point::{Direction, Point},
+    search::{bfs, dijkstra, Graph, Search},
+    solution::{Solution, SolveError},
+    visualize::{Animate, Cell, Frame, Sink, ACTOR, FLOOR, GOAL, TRAIL, WALL},
+};
+
+type Input = Grid<u8>;
+
+/// The reindeer's position and the way it is facing.
+pub type Reindeer = (Point, Direction);
+
+struct Maze<'a>(&'a Grid<u8>);
+
+impl Graph for Maze<'_> {
+    type State = Reindeer;
+
+    fn successors(
+        &self,
+        &(location, facing): &Reindeer,
+    ) -> impl Iterator<Item = (Reindeer, usize)> {
+        facing.no_uturn().into_iter().filter_map(move |dir| {
+            let new = location.navigate(&dir);
+            let score = if facing == dir { 1 } else { 1001 };
+            match self.0.get(&new) {
+                Some(&tile) if tile != b'#' => Some(((new, dir), score)),
+                _ => None,
+            }
+        })
+    }
+}
+
+#[aoc_generator(day16)]
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let grid = Grid::parse_only(input, b"#.SE")?;
+    let start = grid.require(input, b'S')?;
+    let end = grid.require(input, b'E')?;
+    let reachable = bfs(&Maze(&grid), [(start, Direction::East)], |&(p, _)| p == end);
+    if reachable.goals.is_empty() {
+        return Err(grid::error_at(input, end, "no way from `S` leads to `E`"));
+    }
+    Ok(grid)
+}
+
+fn solve(grid: &Grid<u8>) -> Search<Reindeer> {
+    let start = grid.find(&b'S').unwrap();
+    dijkstra(&Maze(grid), [(start, Direction::East)], |&(location, _)| {
+        grid[location] == b'E'
+    })
+}
+
+#[aoc(day16, part1)]
+fn part1(input: &Input) -> usize {
+    solve(input).goal_distance().unwrap()
+}
+
+#[aoc(day16, part2)]
+fn part2(input: &Input) -> usize {
+    let search = solve(input);
+    let mut places_to_sit = input.same_size_with(false);
+    for (point, _) in search.on_cheapest_paths(&search.goals) {
+        places_to_sit[point] = true;
+    }
+    places_to_sit.values().filter(|&&seat| seat).count()
+}
+
+/// Day 16: Reindeer Maze.
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Input;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 16;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part2(input))
+    }
+}
+
+/// Part 1 walks one cheapest path as straight runs between turns. Part 2 lists every seat on
+/// any cheapest path.
+impl Explain for Day16 {
+    fn explain(grid: &Input, _: &(), part: u8) -> Explanation {
+        let search = solve(grid);
+        if part == 2 {
+            let seats = search
+                .on_cheapest_paths(&search.goals)
+                .into_iter()
+                .map(|(point, _)| [point.x, point.y])
+                .sorted()
+                .dedup()
+                .collect_vec();
+            return Explanation::new(seats.len(), json!({ "seats": seats }));
+        }
+        let path = search.path().unwrap_or_default();
+        let mut corner = path.first().map(|&(point, _)| point).unwrap_or_default();
+        let runs = path
+            .iter()
+            .chunk_by(|(_, facing)| *facing)
+            .into_iter()
+            .map(|(facing, run)| {
+                let run = run.collect_vec();
+                let from = corner;
+                corner = run[run.len() - 1].0;
+                json!({
+                    "facing": format!("{facing:?}"),
+                    "from": [from.x, from.y],
+                    "to": [corner.x, corner.y],
+                    "steps": from.manhattan_distance(&corner),
+                })
+            })
+            .collect_vec();
+        let cost = search.goal_distance().unwrap_or_default();
+        Explanation::new(
+            cost,
+            json!({
+                "cost": cost,
+                "steps": path.len().saturating_sub(1),
+                "turns": runs.len().saturating_sub(1),
+                "runs": runs,
+            }),
+        )
+    }
+}
+
+/// Shows the search spreading out from the start in order of cost, then walks one cheapest path
+/// and finally marks every seat that lies on any of them.
+impl Animate for Day16 {
+    fn animate(grid: &Input, _: &(), sink: &mut dyn Sink) -> io::Result<()> {
+        const WAVES: usize = 100;
+        let search = solve(grid);
+        let mut frame = Frame::from_grid(grid, |_, &b| match b {
+            b'#' => Cell::new('#', WALL),
+            b'S' => Cell::new('S', ACTOR),
+            b'E' => Cell::new('E', GOAL),
+            _ => Cell::new('.', FLOOR),
+        });
+        let settled = search
+            .distances
+            .iter()
+            .map(|(&(point, _), &cost)| (cost, point))
+            .sorted()
+            .collect_vec();
+        for wave in settled.chunks(settled.len().div_ceil(WAVES).max(1)) {
+            for &(_, point) in wave.iter().filter(|(_, p)| grid[p] == b'.') {
+                frame.draw(point, '.', TRAIL);
+            }
+            frame.caption = format!("explored up to cost {}", wave[wave.len() - 1].0);
+            sink.frame(&frame)?;
+        }
+        for (point, facing) in search.path().unwrap_or_default() {
+            let arrow = match facing {
+                Direction::North => '^',
+                Direction::East => '>',
+                Direction::South => 'v',
+                Direction::West => '<',
+            };
+            frame.draw(point, arrow, ACTOR);
+            frame.caption = format!("cheapest path, cost {}", search.distances[&(point, facing)]);
+            sink.frame(&frame)?;
+        }
+        let seats: HashSet<_> = search
+            .on_cheapest_paths(&search.goals)
+            .into_iter()
+            .map(|(point, _)| point)
+            .collect();
+        for &seat in &seats {
+            frame.draw(seat, 'O', GOAL);
+        }
+        frame.caption = format!("{} seats on a cheapest path", seats.len());
+        sink.frame(&frame)
+    }
+}
+
+params! {
+    /// Size of the generated maze and how many shortcuts it has.
+    pub struct Knobs {
+        size: usize = 141,
+        /// Chance that a wall between two corridors is knocked out, making loops.
+        loops: f64 = 0.1,
+    }
+}
+
+/// A maze with the start in the bottom left corner and the end in the top right one, like
+/// the real puzzle.
+impl Generate for Day16 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let mut maze = maze(rng, knobs.size, knobs.loops);
+        let far = maze.width as isize - 2;
+        maze[Point::new(1, far)] = b'S';
+        maze[Point::new(far, 1)] = b'E';
+        maze.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str = r#"###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############"#;
+
+    pub const SECOND_EXAMPLE: &str = r#"#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################"#;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 7036);
+        assert_eq!(part1(&parse(SECOND_EXAMPLE).unwrap()), 11048);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 45);
+        assert_eq!(part2(&parse(SECOND_EXAMPLE).unwrap()), 64);
+    }
+
+    #[test]
+    fn rejects_mazes_without_a_way_out() {
+        let error = parse("#####\n#S#E#\n#####").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+    }
+
+    #[test]
+    fn animates_the_search() {
+        let mut frames = Vec::new();
+        Day16::animate(&parse(EXAMPLE).unwrap(), &(), &mut frames).unwrap();
+        let last = frames.last().unwrap();
+        assert_eq!(last.caption, "45 seats on a cheapest path");
+        assert_eq!(last.cells.values().filter(|c| c.glyph == 'O').count(), 45);
+    }
+
+    #[test]
+    fn explains_the_path() {
+        let grid = parse(EXAMPLE).unwrap();
+        let explanation = Day16::explain(&grid, &(), 1);
+        assert_eq!(explanation.answer, "7036");
+        let witness = &explanation.witness;
+        assert_eq!(
+            (&witness["steps"], &witness["turns"]),
+            (&36.into(), &7.into())
+        );
+        assert_eq!(Day16::explain(&grid, &(), 2).answer, "45");
+    }
+}
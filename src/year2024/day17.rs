@@ -0,0 +1,380 @@
+use std::fmt::Display;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+
+use nom::{combinator::verify, error::context};
+
+use crate::{
+    combinators::{comma_list, finish, key_value, line, then_line, two_sections, unsigned, Res},
+    generate::{Generate, Rng},
+    metrics,
+    parse::ParseError,
+    solution::{Solution, SolveError},
+};
+
+#[derive(Clone, Debug)]
+pub struct Computer {
+    a: i64,
+    b: i64,
+    c: i64,
+
+    output: Vec<i64>,
+
+    instructions: Vec<i64>,
+    pointer: usize,
+}
+
+/// Far more steps than any program from the puzzle takes, which halt after a few hundred.
+const MAX_STEPS: usize = 1 << 20;
+
+impl Computer {
+    fn get_next(&mut self) -> Option<(i64, i64)> {
+        if let Some((a, b)) = self
+            .instructions
+            .get(self.pointer)
+            .zip(self.instructions.get(self.pointer + 1))
+        {
+            return Some((*a, *b));
+        }
+        None
+    }
+    fn get_combo(&self, v: i64) -> Result<i64, SolveError> {
+        match v {
+            0..=3 => Ok(v),
+            4 => Ok(self.a),
+            5 => Ok(self.b),
+            6 => Ok(self.c),
+            _ => Err(SolveError::new(format!(
+                "combo operand {v} at {} is reserved",
+                self.pointer + 1
+            ))),
+        }
+    }
+    /// `A` divided by two to the power of the combo operand, rounded down.
+    fn divide(&self, v: i64) -> Result<i64, SolveError> {
+        let shift = u32::try_from(self.get_combo(v)?).unwrap_or(u32::MAX);
+        Ok(self.a.checked_shr(shift).unwrap_or(0))
+    }
+    fn instruction(&mut self, instr: i64, literal: i64) -> Result<(), SolveError> {
+        match instr {
+            0 => self.a = self.divide(literal)?,
+            1 => self.b ^= literal,
+            2 => self.b = self.get_combo(literal)? % 8,
+            3 => {
+                if self.a != 0 {
+                    self.pointer = literal as usize;
+                    return Ok(());
+                }
+            }
+            4 => self.b ^= self.c,
+            5 => self.output.push(self.get_combo(literal)? % 8),
+            6 => self.b = self.divide(literal)?,
+            _ => self.c = self.divide(literal)?,
+        }
+
+        self.pointer += 2;
+        Ok(())
+    }
+
+    fn run_to_end(&mut self) -> Result<(), SolveError> {
+        for _ in 0..MAX_STEPS {
+            match self.get_next() {
+                None => return Ok(()),
+                Some((i, v)) => self.instruction(i, v)?,
+            }
+        }
+        Err(SolveError::new(format!(
+            "the program is still running after {MAX_STEPS} steps"
+        )))
+    }
+}
+
+fn registers(input: &str) -> Res<'_, ((i64, i64), i64)> {
+    then_line(
+        then_line(
+            key_value("Register A", unsigned),
+            key_value("Register B", unsigned),
+        ),
+        key_value("Register C", unsigned),
+    )(input)
+}
+
+fn program(input: &str) -> Res<'_, Vec<i64>> {
+    key_value(
+        "Program",
+        comma_list(context(
+            "a 3-bit instruction",
+            verify(unsigned, |i: &i64| (0..8).contains(i)),
+        )),
+    )(input)
+}
+
+#[aoc_generator(day17)]
+fn parse(input: &str) -> Result<Computer, ParseError> {
+    let (((a, b), c), instructions) = finish(input, two_sections(registers, line(program)))?;
+    Ok(Computer {
+        a,
+        b,
+        c,
+        output: vec![],
+        instructions,
+        pointer: 0,
+    })
+}
+
+#[aoc(day17, part1)]
+fn part1(input: &Computer) -> Result<String, SolveError> {
+    let mut cpu = input.clone();
+    cpu.run_to_end()?;
+    Ok(cpu.output.iter().join(","))
+}
+
+#[aoc(day17, part2)]
+fn part2(input: &Computer) -> Result<u64, SolveError> {
+    let len = input.instructions.len();
+    // Beyond `8^len`, A has more octal digits than the program has instructions, so it prints
+    // too much. Past 21 digits it wouldn't fit in a register either.
+    if len > 21 {
+        return Err(SolveError::new("the program is too long to print itself"));
+    }
+    let limit = 8u64.pow(len as u32);
+    let mut factors = vec![0; len];
+    loop {
+        metrics::record(|m| m.iterations += 1);
+        let mut init_a = 0;
+        for (i, f) in factors.iter().enumerate() {
+            init_a += 8u64.pow(i as u32) * f;
+        }
+        if init_a >= limit {
+            return Err(SolveError::new(
+                "no value of A makes the program print itself",
+            ));
+        }
+        let mut cpu = Computer {
+            a: init_a as i64,
+            ..input.clone()
+        };
+        cpu.run_to_end()?;
+        if cpu.output == input.instructions {
+            return Ok(init_a);
+        }
+        let mismatch = (0..len)
+            .rev()
+            .find(|&i| cpu.output.get(i) != Some(&cpu.instructions[i]))
+            .unwrap_or(len - 1);
+        factors[mismatch] += 1;
+    }
+}
+
+/// Day 17: Chronospatial Computer.
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Computer;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 17;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        part2(input)
+    }
+}
+
+/// Programs of the kind the real puzzle hands out, which print their own code when `A` is
+/// just right: every pass they output a function of the low bits of `A` and shift it along by
+/// three bits, until it runs out. Only programs where some value of `A` makes a copy are kept,
+/// so part 2 always has an answer.
+impl Generate for Day17 {
+    type Knobs = ();
+
+    fn generate(rng: &mut Rng, _: &()) -> String {
+        loop {
+            // bst A, bxl X, cdv B, then bxl Y, bxc and adv 3 in any order, out B, jnz 0.
+            let mut middle = [[1, rng.between(0, 7)], [4, rng.between(0, 7)], [0, 3]];
+            rng.shuffle(&mut middle);
+            let instructions = [[2, 4], [1, rng.between(0, 7)], [7, 5]]
+                .into_iter()
+                .chain(middle)
+                .chain([[5, 5], [3, 0]])
+                .flatten()
+                .map(|i| i as i64)
+                .collect_vec();
+            let computer = Computer {
+                a: 0,
+                b: 0,
+                c: 0,
+                output: vec![],
+                instructions,
+                pointer: 0,
+            };
+            if quine(&computer, 0, computer.instructions.len()).is_some() {
+                let a = rng.next_u64() >> 16;
+                return format!(
+                    "Register A: {a}\nRegister B: 0\nRegister C: 0\n\nProgram: {}",
+                    computer.instructions.iter().join(",")
+                );
+            }
+        }
+    }
+}
+
+/// The smallest `A` that makes `computer` print the last `remaining` instructions of its own
+/// program, given the higher bits of `A` already chosen in `a`.
+fn quine(computer: &Computer, a: i64, remaining: usize) -> Option<i64> {
+    if remaining == 0 {
+        return Some(a);
+    }
+    let wanted = &computer.instructions[remaining - 1..];
+    (0..8).find_map(|bits| {
+        let a = a << 3 | bits;
+        if a == 0 {
+            return None;
+        }
+        let mut cpu = Computer {
+            a,
+            ..computer.clone()
+        };
+        if cpu.run_to_end().is_ok() && cpu.output == wanted {
+            quine(computer, a, remaining - 1)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str = r#"Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0"#;
+
+    pub const CHECK_BEHAVIOUR: &str = r#"Register A: 0
+Register B: 0
+Register C: 9
+
+Program: 2,6
+    "#;
+
+    #[test]
+    fn check_behaviour() {
+        let mut cp = parse(CHECK_BEHAVIOUR).unwrap();
+        cp.run_to_end().unwrap();
+        assert_eq!(cp.b, 1);
+    }
+
+    #[test]
+    fn verify_states() {
+        let mut cp = parse(
+            r#"Register A: 10
+Register B: 0
+Register C: 0
+
+Program: 5,0,5,1,5,4"#,
+        )
+        .unwrap();
+        cp.run_to_end().unwrap();
+        assert_eq!(cp.output, vec![0, 1, 2]);
+    }
+
+    #[test]
+    pub fn year_example() {
+        let mut cp = parse(
+            r#"Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0"#,
+        )
+        .unwrap();
+        cp.run_to_end().unwrap();
+        assert_eq!(cp.a, 0);
+        assert_eq!(cp.output, vec![4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
+    }
+
+    #[test]
+    pub fn math_works() {
+        let mut cp = parse(
+            r#"Register A: 0
+Register B: 29
+Register C: 0
+
+Program: 1,7"#,
+        )
+        .unwrap();
+        cp.run_to_end().unwrap();
+        assert_eq!(cp.b, 26);
+    }
+
+    #[test]
+    pub fn years_are_magic() {
+        let mut cp = parse(
+            r#"Register A: 0
+Register B: 2024
+Register C: 43690
+
+Program: 4,0"#,
+        )
+        .unwrap();
+        cp.run_to_end().unwrap();
+        assert_eq!(cp.b, 44354);
+    }
+
+    #[test]
+    fn rejects_instructions_wider_than_three_bits() {
+        let error =
+            parse("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 0,8").unwrap_err();
+        assert_eq!((error.line, error.column), (5, 12));
+        assert_eq!(error.message, "expected a 3-bit instruction");
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            part1(&parse(EXAMPLE).unwrap()).unwrap(),
+            "4,6,3,5,6,3,5,2,1,0"
+        );
+    }
+
+    #[test]
+    fn generated_programs_can_print_themselves() {
+        let mut rng = Rng::new(17);
+        let computer = parse(&Day17::generate(&mut rng, &())).unwrap();
+        let a = part2(&computer).unwrap();
+        let mut cpu = Computer {
+            a: a as i64,
+            ..computer.clone()
+        };
+        cpu.run_to_end().unwrap();
+        assert_eq!(cpu.output, computer.instructions);
+        assert_eq!(Some(a as i64), quine(&computer, 0, 16));
+    }
+
+    #[test]
+    fn reports_programs_without_an_answer() {
+        let program = |a: u64, program: &str| {
+            parse(&format!(
+                "Register A: {a}\nRegister B: 0\nRegister C: 0\n\nProgram: {program}"
+            ))
+            .unwrap()
+        };
+        let error = part1(&program(1, "5,7")).unwrap_err();
+        assert_eq!(error.message, "combo operand 7 at 1 is reserved");
+        let error = part1(&program(1, "3,0")).unwrap_err();
+        assert!(error.message.contains("still running"));
+        assert!(part2(&program(0, "1,7")).is_err());
+    }
+}
@@ -0,0 +1,379 @@
+use std::{collections::VecDeque, fmt::Display, io};
+
+use ahash::{HashMap, HashSet};
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::{
+    generate::{Generate, Rng},
+    metrics,
+    params::params,
+    parse::{self, number, ParseError},
+    point::Point,
+    search::{bfs, Graph},
+    solution::{Solution, SolveError},
+    visualize::{Animate, Frame, Sink, DANGER, OBJECT, TRAIL},
+};
+#[aoc_generator(day18)]
+fn parse(input: &str) -> Result<Vec<Point>, ParseError> {
+    parse::lines(input)
+        .map(|l| {
+            let (x, y) = parse::split_once(input, l, ",")?;
+            Ok(Point::new(number(input, x)?, number(input, y)?))
+        })
+        .collect()
+}
+
+struct MemorySpace {
+    corrupted: HashSet<Point>,
+    end: Point,
+}
+
+impl Graph for MemorySpace {
+    type State = Point;
+
+    fn successors(&self, point: &Point) -> impl Iterator<Item = (Point, usize)> {
+        point
+            .cardinal_neighbours()
+            .into_iter()
+            .filter(|p| p.x >= 0 && p.y >= 0 && p.x <= self.end.x && p.y <= self.end.y)
+            .filter(|p| !self.corrupted.contains(p))
+            .map(|p| (p, 1))
+    }
+}
+
+fn solve(corrupted: HashSet<Point>, end: Point) -> Option<usize> {
+    let space = MemorySpace { corrupted, end };
+    bfs(&space, [Point::new(0, 0)], |&p| p == end).goal_distance()
+}
+
+/// One shortest way out, empty if the exit can't be reached.
+fn shortest_path(space: &MemorySpace) -> Vec<Point> {
+    bfs(space, [Point::new(0, 0)], |&p| p == space.end)
+        .path()
+        .unwrap_or_default()
+}
+
+/// The widest memory space searched, which keeps every search to some 16 million locations.
+const MAX_SIZE: isize = 4096;
+
+params! {
+    /// The width and height of the memory space, from 1 to 4096, and how many bytes part 1
+    /// lets fall.
+    pub struct Params {
+        size: isize = 71,
+        bytes: usize = 1024,
+    }
+}
+
+impl Knobs {
+    /// The size asked for, made to fit the limits of [`Params`], and the bytes that can fall
+    /// off the way out: every tile but the corners and the path between them.
+    fn fit(&self) -> (isize, usize) {
+        let size = self.size.clamp(2, MAX_SIZE);
+        (size, self.bytes.min((size as usize - 1).pow(2)))
+    }
+}
+
+impl Params {
+    fn exit(&self) -> Result<Point, SolveError> {
+        if !(1..=MAX_SIZE).contains(&self.size) {
+            return Err(SolveError::new(format!(
+                "the memory space is from 1 to {MAX_SIZE} wide, not {}",
+                self.size
+            )));
+        }
+        Ok(Point::new(self.size - 1, self.size - 1))
+    }
+}
+
+fn part1_with(input: &[Point], params: &Params) -> Result<usize, SolveError> {
+    let exit = params.exit()?;
+    solve(
+        input
+            .iter()
+            .take(params.bytes)
+            .cloned()
+            .collect::<HashSet<_>>(),
+        exit,
+    )
+    .ok_or_else(|| SolveError::new(format!("the first {} bytes cut off the exit", params.bytes)))
+}
+
+#[aoc(day18, part1)]
+fn part1(input: &[Point]) -> Result<usize, SolveError> {
+    part1_with(input, &Params::default())
+}
+
+fn blocking_byte(corrupted_locations: &[Point], exit: Point) -> Option<Point> {
+    let byte_indexes = corrupted_locations.iter().enumerate().fold(
+        HashMap::default(),
+        |mut indexes, (index, location)| {
+            indexes.entry(location).or_insert(index);
+            indexes
+        },
+    );
+
+    // Every location is labelled with the latest byte index at which some path from the start
+    // still reaches it, `len` meaning no byte ever cuts it off. Like part 1, a byte landing
+    // where you start doesn't stop you from leaving.
+    let start = Point::new(0, 0);
+    let never = corrupted_locations.len();
+    let mut queue = VecDeque::from([(start, never)]);
+    let mut visited = HashMap::default();
+    visited.insert(start, never);
+
+    let mut exit_index = if start == exit { never } else { 0 };
+
+    while let Some((position, index)) = queue.pop_front() {
+        metrics::record(|m| m.nodes_expanded += 1);
+        for next_position in [
+            Point::new(position.x + 1, position.y),
+            Point::new(position.x - 1, position.y),
+            Point::new(position.x, position.y + 1),
+            Point::new(position.x, position.y - 1),
+        ] {
+            if next_position.x >= 0
+                && next_position.x <= exit.x
+                && next_position.y >= 0
+                && next_position.y <= exit.y
+            {
+                let index = match byte_indexes.get(&next_position) {
+                    Some(&corrupted_index) if corrupted_index < index => corrupted_index,
+                    _ => index,
+                };
+
+                if next_position == exit {
+                    exit_index = exit_index.max(index);
+                }
+
+                if let Some(prev_index) = visited.get(&next_position) {
+                    if *prev_index >= index {
+                        continue;
+                    }
+                }
+
+                metrics::record(|m| m.pushes += 1);
+                queue.push_back((next_position, index));
+                visited.insert(next_position, index);
+            }
+        }
+    }
+
+    corrupted_locations.get(exit_index).copied()
+}
+
+fn part2_with(input: &[Point], params: &Params) -> Result<Point, SolveError> {
+    blocking_byte(input, params.exit()?)
+        .ok_or_else(|| SolveError::new("the exit stays reachable after every byte"))
+}
+
+#[aoc(day18, part2)]
+fn part2(input: &[Point]) -> Result<Point, SolveError> {
+    part2_with(input, &Params::default())
+}
+
+/// Day 18: RAM Run.
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Point>;
+    type Params = Params;
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 18;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        part1_with(input, params)
+    }
+
+    fn part2(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        part2_with(input, params)
+    }
+}
+
+/// Drops the bytes one at a time, finding a new way out whenever one lands on the current path,
+/// until a byte cuts the exit off.
+impl Animate for Day18 {
+    fn animate(input: &Vec<Point>, params: &Params, sink: &mut dyn Sink) -> io::Result<()> {
+        let end = params.exit().map_err(|e| io::Error::other(e.message))?;
+        let size = params.size as usize;
+        let mut space = MemorySpace {
+            corrupted: HashSet::default(),
+            end,
+        };
+        let mut path = shortest_path(&space);
+        for (fallen, &byte) in input.iter().enumerate() {
+            space.corrupted.insert(byte);
+            if path.contains(&byte) {
+                path = shortest_path(&space);
+            }
+            let mut frame = Frame::new(size, size);
+            for &point in &space.corrupted {
+                frame.draw(point, '#', OBJECT);
+            }
+            for &point in &path {
+                frame.draw(point, 'O', TRAIL);
+            }
+            if path.is_empty() {
+                frame.draw(byte, 'X', DANGER);
+                frame.caption = format!("byte {} at {byte} cuts off the exit", fallen + 1);
+                return sink.frame(&frame);
+            }
+            frame.caption = format!("{} bytes fallen, {} steps out", fallen + 1, path.len() - 1);
+            sink.frame(&frame)?;
+        }
+        Ok(())
+    }
+}
+
+params! {
+    /// Size of the generated memory space and when the way out must still be open.
+    pub struct Knobs {
+        size: isize = 71,
+        /// How many bytes have to leave a way out for part 1.
+        bytes: usize = 1024,
+    }
+}
+
+/// Every location but the corners falls in random order, the first `bytes` of them missing a
+/// winding path from one corner to the other, so there is a way out after those and none at
+/// the end.
+impl Generate for Day18 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let (size, bytes) = knobs.fit();
+        let exit = Point::new(size - 1, size - 1);
+        let mut path = HashSet::from_iter([Point::new(0, 0)]);
+        let mut point = Point::new(0, 0);
+        while point != exit {
+            let right = point.x < exit.x && (point.y == exit.y || rng.chance(0.5));
+            point = Point::new(point.x + right as isize, point.y + !right as isize);
+            path.insert(point);
+        }
+        let (mut later, mut early): (Vec<_>, Vec<_>) = (0..size)
+            .flat_map(|y| (0..size).map(move |x| Point::new(x, y)))
+            .filter(|p| *p != Point::new(0, 0) && *p != exit)
+            .partition(|p| path.contains(p));
+        rng.shuffle(&mut early);
+        later.extend(early.drain(bytes..));
+        rng.shuffle(&mut later);
+        early
+            .iter()
+            .chain(&later)
+            .map(|p| format!("{},{}", p.x, p.y))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn params_for(knobs: &Knobs) -> Vec<(String, String)> {
+        let (size, bytes) = knobs.fit();
+        vec![
+            ("size".to_string(), size.to_string()),
+            ("bytes".to_string(), bytes.to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    pub const EXAMPLE: &str = r#"5,4
+    4,2
+    4,5
+    3,0
+    2,1
+    6,3
+    2,4
+    1,5
+    0,6
+    3,3
+    2,6
+    5,1
+    1,2
+    5,5
+    2,5
+    6,5
+    1,4
+    0,4
+    6,4
+    1,1
+    6,1
+    1,0
+    0,5
+    1,6
+    2,0"#;
+
+    #[test]
+    fn part1_example() {
+        let corrupted = parse(EXAMPLE).unwrap();
+        let corrupted = corrupted.into_iter().take(12).collect::<HashSet<_>>();
+        assert_eq!(solve(corrupted, Point::new(6, 6)), Some(22));
+    }
+
+    #[test]
+    fn examples_end_to_end() {
+        let params = Params { size: 7, bytes: 12 };
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(Day18::part1(&input, &params).unwrap().to_string(), "22");
+        assert_eq!(part2_with(&input, &params), Ok(Point::new(6, 1)));
+        let every_byte = Params { size: 7, bytes: 25 };
+        assert!(part1_with(&input, &every_byte).is_err());
+        for size in [0, isize::MIN, MAX_SIZE + 1] {
+            let params = Params { size, bytes: 12 };
+            let error = part1_with(&input, &params).unwrap_err();
+            assert_eq!(
+                error.message,
+                format!("the memory space is from 1 to 4096 wide, not {size}")
+            );
+            assert!(part2_with(&input, &params).is_err());
+        }
+    }
+
+    #[test]
+    fn part2_example() {
+        // The example's bytes all land near the start and never wall it in on the full space.
+        let exit = Params::default().exit().unwrap();
+        assert_eq!(blocking_byte(&parse(EXAMPLE).unwrap(), exit), None);
+    }
+
+    /// Part 2 the slow way: drop the bytes one at a time until part 1 finds no way out.
+    fn first_blocking_byte(bytes: &[Point], exit: Point) -> Option<Point> {
+        (1..=bytes.len())
+            .find(|&fallen| solve(bytes[..fallen].iter().copied().collect(), exit).is_none())
+            .map(|fallen| bytes[fallen - 1])
+    }
+
+    fn falling_bytes() -> impl Strategy<Value = (isize, Vec<Point>)> {
+        (1isize..8).prop_flat_map(|size| {
+            let byte = (0..size, 0..size).prop_map(|(x, y)| Point::new(x, y));
+            let count = (size * size * 2) as usize;
+            (Just(size), prop::collection::vec(byte, 0..count))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn blocking_byte_agrees_with_brute_force((size, bytes) in falling_bytes()) {
+            let exit = Point::new(size - 1, size - 1);
+            prop_assert_eq!(blocking_byte(&bytes, exit), first_blocking_byte(&bytes, exit));
+        }
+    }
+
+    #[test]
+    fn animates_until_the_exit_is_cut_off() {
+        let params = Params { size: 7, bytes: 12 };
+        let mut frames = Vec::new();
+        Day18::animate(&parse(EXAMPLE).unwrap(), &params, &mut frames).unwrap();
+        assert_eq!(frames[11].caption, "12 bytes fallen, 22 steps out");
+        assert_eq!(frames.len(), 21);
+        assert_eq!(frames[20].caption, "byte 21 at (6, 1) cuts off the exit");
+    }
+}
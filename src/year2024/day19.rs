@@ -0,0 +1,249 @@
+use std::fmt::Display;
+
+use ahash::{HashMap, HashSet};
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::{
+    generate::{Generate, Rng},
+    metrics,
+    params::params,
+    parse::{self, ParseError},
+    solution::{Solution, SolveError},
+};
+
+pub struct Onsen {
+    pub towels: HashSet<String>,
+    pub displays: Vec<String>,
+}
+
+/// Checks that `stripes`, a slice of `input`, only holds the colours `w`, `u`, `b`, `r` and `g`.
+fn colours<'a>(input: &str, stripes: &'a str) -> Result<&'a str, ParseError> {
+    match stripes.char_indices().find(|&(_, c)| !"wubrg".contains(c)) {
+        Some((i, c)) => Err(ParseError::at(
+            input,
+            &stripes[i..i + c.len_utf8()],
+            "expected a stripe colour, one of `wubrg`",
+        )),
+        None => Ok(stripes),
+    }
+}
+
+#[aoc_generator(day19)]
+fn parse(input: &str) -> Result<Onsen, ParseError> {
+    let [available, desired] = parse::exact_sections(input)?;
+    let towels = available
+        .split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(|t| colours(input, t).map(str::to_string))
+        .collect::<Result<HashSet<_>, _>>()?;
+    if towels.is_empty() {
+        return Err(ParseError::at(
+            input,
+            available,
+            "expected a list of towels",
+        ));
+    }
+    Ok(Onsen {
+        towels,
+        displays: parse::lines(desired)
+            .map(|l| colours(input, l).map(str::to_string))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+pub fn count_valid_patterns<'a>(
+    pattern: &'a str,
+    stripes: &HashSet<String>,
+    already_computed: &mut HashMap<&'a str, usize>,
+    max_length: usize,
+) -> usize {
+    count_from(pattern, 0, stripes, already_computed, max_length)
+}
+
+/// Ways to make the rest of a pattern, `depth` towels into it.
+fn count_from<'a>(
+    pattern: &'a str,
+    depth: usize,
+    stripes: &HashSet<String>,
+    already_computed: &mut HashMap<&'a str, usize>,
+    max_length: usize,
+) -> usize {
+    let mut combinations = 0;
+    metrics::record(|m| m.reach(depth));
+    if already_computed.contains_key(pattern) {
+        metrics::record(|m| m.memo_hits += 1);
+        return *already_computed.get(pattern).unwrap();
+    }
+    if pattern.is_empty() {
+        return 1;
+    }
+    metrics::record(|m| m.memo_misses += 1);
+    for i in 1..=max_length.min(pattern.len()) {
+        if stripes.contains(&pattern[..i].to_string()) {
+            combinations += count_from(
+                &pattern[i..],
+                depth + 1,
+                stripes,
+                already_computed,
+                max_length,
+            );
+        }
+    }
+    already_computed.insert(pattern, combinations);
+    combinations
+}
+
+#[aoc(day19, part1)]
+fn part1(input: &Onsen) -> usize {
+    let max_length = input.towels.iter().map(|v| v.len()).max().unwrap();
+    let mut already_computed = HashMap::default();
+    input
+        .displays
+        .iter()
+        .filter(|p| count_valid_patterns(p, &input.towels, &mut already_computed, max_length) > 0)
+        .count()
+}
+
+#[aoc(day19, part2)]
+fn part2(input: &Onsen) -> usize {
+    let max_length = input.towels.iter().map(|v| v.len()).max().unwrap();
+    let mut already_computed = HashMap::default();
+    input
+        .displays
+        .iter()
+        .map(|p| count_valid_patterns(p, &input.towels, &mut already_computed, max_length))
+        .sum()
+}
+
+/// Day 19: Linen Layout.
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Onsen;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 19;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part2(input))
+    }
+}
+
+params! {
+    /// How many towels and designs to generate.
+    pub struct Knobs {
+        towels: usize = 447,
+        designs: usize = 400,
+        /// Chance that a design is made by laying out towels rather than picking colours.
+        possible: f64 = 0.5,
+        longest_design: usize = 60,
+    }
+}
+
+/// Towels of one to eight stripes, among them every single colour but one. No towel ends with
+/// the missing colour or has it twice in a row, so designs that do can't be made.
+impl Generate for Day19 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        const COLOURS: [char; 5] = ['w', 'u', 'b', 'r', 'g'];
+        let missing = *rng.pick(&COLOURS);
+        let mut towels: Vec<String> = COLOURS
+            .iter()
+            .filter(|&&c| c != missing)
+            .map(|c| c.to_string())
+            .collect();
+        let mut seen: HashSet<String> = towels.iter().cloned().collect();
+        seen.insert(missing.to_string());
+        // Short towels run out quickly, and after a while so can everything else.
+        for _ in 0..knobs.towels * 10 {
+            if towels.len() >= knobs.towels {
+                break;
+            }
+            let length = rng.between(2, 8) as usize;
+            let towel: String = (0..length).map(|_| *rng.pick(&COLOURS)).collect();
+            let awkward =
+                towel.ends_with(missing) || towel.contains(&missing.to_string().repeat(2));
+            if !awkward && seen.insert(towel.clone()) {
+                towels.push(towel);
+            }
+        }
+        let longest = knobs.longest_design.max(1);
+        let designs = (0..knobs.designs).map(|_| {
+            let length = rng.between(longest as isize / 3 + 1, longest as isize) as usize;
+            let mut design = String::new();
+            if rng.chance(knobs.possible) {
+                while design.len() < length {
+                    let towel = rng.pick(&towels);
+                    if design.len() + towel.len() > longest {
+                        break;
+                    }
+                    design.push_str(towel);
+                }
+            }
+            while design.len() < length {
+                design.push(*rng.pick(&COLOURS));
+            }
+            design
+        });
+        format!(
+            "{}\n\n{}",
+            towels.join(", "),
+            designs.collect::<Vec<_>>().join("\n")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str = r#"r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb"#;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 6);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 16);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn counts_towels_deep() {
+        let (count, work) = crate::metrics::measure(|| part2(&parse("r, rr\n\nrrr").unwrap()));
+        assert_eq!(count, 3);
+        // `r` three times takes the most towels.
+        assert_eq!(work.max_depth, 3);
+    }
+
+    #[test]
+    fn rejects_unknown_colours() {
+        let Err(error) = parse("r, wr\n\nwrx") else {
+            panic!("`x` is not a colour");
+        };
+        assert_eq!((error.line, error.column), (3, 3));
+        assert!(parse("r, wé\n\nwr").is_err());
+    }
+}
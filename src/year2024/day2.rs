@@ -0,0 +1,365 @@
+use std::{fmt::Display, ops::RangeInclusive};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+use serde_json::json;
+
+use crate::{
+    explain::{Explain, Explanation},
+    generate::{Generate, Rng},
+    params::params,
+    parse::{self, numbers, ParseError},
+    solution::{Solution, SolveError},
+    stream::Stream,
+};
+
+pub type Report = Vec<i32>;
+pub type Reports = Vec<Report>;
+#[aoc_generator(day2)]
+fn parse(input: &str) -> Result<Reports, ParseError> {
+    parse::lines(input)
+        .map(|l| numbers(input, l, ' '))
+        .collect()
+}
+
+params! {
+    /// How far apart neighbouring levels may be, and how many bad levels the Problem Dampener
+    /// takes out in part 2.
+    pub struct Params {
+        min_step: i64 = 1,
+        max_step: i64 = 3,
+        dampener: usize = 1,
+    }
+}
+
+/// Decides whether reports are safe: every level moves the same way as the one before, by a
+/// step in `steps`, once at most `tolerance` levels are taken out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validator {
+    pub steps: RangeInclusive<i64>,
+    pub tolerance: usize,
+}
+
+/// How a report was made safe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verdict {
+    /// Whether the levels that are left go up rather than down.
+    pub increasing: bool,
+    /// Indices of the levels taken out, in order.
+    pub removed: Vec<usize>,
+}
+
+impl Validator {
+    /// The fewest levels to take out to make `report` safe, or `None` if that takes more than
+    /// the tolerance. Empty and single level reports are safe as they are.
+    pub fn check(&self, report: &[i32]) -> Option<Verdict> {
+        [true, false]
+            .into_iter()
+            .filter_map(|increasing| self.check_direction(report, increasing))
+            .min_by_key(|verdict| verdict.removed.len())
+    }
+
+    fn fits(&self, from: i32, to: i32, increasing: bool) -> bool {
+        let step = to as i64 - from as i64;
+        self.steps.contains(&if increasing { step } else { -step })
+    }
+
+    /// Keeps the longest run of levels going one way, in O(levels * tolerance). A kept level
+    /// can only follow one of the `tolerance + 1` levels before it, as skipping more than that
+    /// would already take out too many.
+    fn check_direction(&self, report: &[i32], increasing: bool) -> Option<Verdict> {
+        // For every level: the fewest removals up to it if it's kept, and the kept level before.
+        let mut fewest: Vec<(usize, Option<usize>)> = Vec::with_capacity(report.len());
+        for (i, &level) in report.iter().enumerate() {
+            let mut best = (i, None);
+            for j in i.saturating_sub(self.tolerance.saturating_add(1))..i {
+                let removed = fewest[j].0 + (i - j - 1);
+                if removed < best.0 && self.fits(report[j], level, increasing) {
+                    best = (removed, Some(j));
+                }
+            }
+            fewest.push(best);
+        }
+        let Some((last, removed)) = fewest
+            .iter()
+            .enumerate()
+            .map(|(i, &(removed, _))| (i, removed + report.len() - 1 - i))
+            .min_by_key(|&(_, removed)| removed)
+        else {
+            return Some(Verdict {
+                increasing,
+                removed: Vec::new(),
+            });
+        };
+        if removed > self.tolerance {
+            return None;
+        }
+        let mut kept = vec![false; report.len()];
+        let mut at = Some(last);
+        while let Some(i) = at {
+            kept[i] = true;
+            at = fewest[i].1;
+        }
+        Some(Verdict {
+            increasing,
+            removed: (0..report.len()).filter(|&i| !kept[i]).collect(),
+        })
+    }
+}
+
+/// The validator for `part`: part 1 takes nothing out, part 2 uses the dampener.
+fn validator(params: &Params, part: u8) -> Validator {
+    Validator {
+        steps: params.min_step..=params.max_step,
+        tolerance: if part == 1 { 0 } else { params.dampener },
+    }
+}
+
+fn count_safe(input: &Reports, params: &Params, part: u8) -> usize {
+    let validator = validator(params, part);
+    input
+        .iter()
+        .filter(|report| validator.check(report).is_some())
+        .count()
+}
+
+#[aoc(day2, part1)]
+fn part1(input: &Reports) -> usize {
+    count_safe(input, &Params::default(), 1)
+}
+
+#[aoc(day2, part2)]
+fn part2(input: &Reports) -> usize {
+    count_safe(input, &Params::default(), 2)
+}
+
+/// Day 2: Red-Nosed Reports.
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Reports;
+    type Params = Params;
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 2;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(count_safe(input, params, 1))
+    }
+
+    fn part2(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(count_safe(input, params, 2))
+    }
+}
+
+/// Counts the reports safe as they are, and those the dampener makes safe.
+impl Stream for Day2 {
+    type State = [usize; 2];
+
+    fn fold(
+        safe_reports: &mut [usize; 2],
+        record: &str,
+        params: &Params,
+    ) -> Result<(), ParseError> {
+        let reports = parse(record)?;
+        for part in 1..=2 {
+            safe_reports[part - 1] += count_safe(&reports, params, part as u8);
+        }
+        Ok(())
+    }
+
+    fn answer(safe_reports: &[usize; 2], _: &Params, part: u8) -> Result<String, SolveError> {
+        Ok(safe_reports[part as usize - 1].to_string())
+    }
+}
+
+/// Lists the levels taken out of each report the dampener saves, and the reports that stay
+/// unsafe, by their position in the input counting from 0.
+impl Explain for Day2 {
+    fn explain(input: &Reports, params: &Params, part: u8) -> Explanation {
+        let validator = validator(params, part);
+        let mut dampened = Vec::new();
+        let mut unsafe_reports = Vec::new();
+        for (i, report) in input.iter().enumerate() {
+            match validator.check(report) {
+                Some(verdict) if verdict.removed.is_empty() => {}
+                Some(verdict) => dampened.push(json!({ "report": i, "removed": verdict.removed })),
+                None => unsafe_reports.push(i),
+            }
+        }
+        Explanation::new(
+            input.len() - unsafe_reports.len(),
+            json!({ "dampened": dampened, "unsafe": unsafe_reports }),
+        )
+    }
+}
+
+params! {
+    /// How many reports to generate, how long they are and how often they're broken.
+    pub struct Knobs {
+        reports: usize = 1000,
+        min_levels: usize = 5,
+        max_levels: usize = 8,
+        /// Chance that a report has a bad level, and half of that that it has two.
+        broken: f64 = 0.6,
+    }
+}
+
+/// Safe reports, some of them broken by a level that repeats, jumps or turns around.
+impl Generate for Day2 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let min = knobs.min_levels.max(2);
+        let max = knobs.max_levels.max(min);
+        (0..knobs.reports)
+            .map(|_| {
+                let direction = if rng.chance(0.5) { 1 } else { -1 };
+                let mut report = vec![rng.between(25, 75)];
+                for _ in 1..min + rng.below(max - min + 1) {
+                    let last = report[report.len() - 1];
+                    report.push(last + direction * rng.between(1, 3));
+                }
+                for chance in [knobs.broken, knobs.broken / 2.0] {
+                    if rng.chance(chance) {
+                        let i = rng.below(report.len() - 1) + 1;
+                        report[i] = report[i - 1]
+                            + match rng.below(3) {
+                                0 => 0,
+                                1 => direction * rng.between(4, 7),
+                                _ => -direction * rng.between(1, 3),
+                            };
+                    }
+                }
+                report.iter().join(" ")
+            })
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            part1(
+                &parse(
+                    r#"7 6 4 2 1
+        1 2 7 8 9
+        9 7 6 2 1
+        1 3 2 4 5
+        8 6 4 4 1
+        1 3 6 7 9"#
+                )
+                .unwrap()
+            ),
+            2
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            part2(
+                &parse(
+                    r#"7 6 4 2 1
+        1 2 7 8 9
+        9 7 6 2 1
+        1 3 2 4 5
+        8 6 4 4 1
+        1 3 6 7 9"#
+                )
+                .unwrap()
+            ),
+            4
+        );
+    }
+
+    #[test]
+    fn single_levels_are_safe() {
+        let reports = parse("5\n1 9\n7 6 9 12").unwrap();
+        assert_eq!(part1(&reports), 1);
+        assert_eq!(part2(&reports), 3);
+    }
+
+    #[test]
+    fn says_which_levels_were_taken_out() {
+        let reports = parse("7 6 4 2 1\n1 2 7 8 9\n1 3 2 4 5\n8 6 4 4 1").unwrap();
+        let explanation = Day2::explain(&reports, &Params::default(), 2);
+        assert_eq!(explanation.answer, "3");
+        assert_eq!(
+            explanation.witness,
+            json!({
+                "dampened": [{"report": 2, "removed": [2]}, {"report": 3, "removed": [3]}],
+                "unsafe": [1],
+            })
+        );
+        let validator = Validator {
+            steps: 1..=3,
+            tolerance: 2,
+        };
+        let verdict = validator.check(&[1, 2, 9, 3, 9, 4]).unwrap();
+        assert_eq!((verdict.increasing, verdict.removed), (true, vec![2, 4]));
+        assert!(validator.check(&[]).unwrap().removed.is_empty());
+        assert_eq!(
+            validator
+                .check(&[i32::MIN, i32::MAX])
+                .unwrap()
+                .removed
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn steps_can_be_configured() {
+        let reports = parse("1 5 9\n1 1 1").unwrap();
+        let wide = Params {
+            min_step: 0,
+            max_step: 4,
+            ..Params::default()
+        };
+        assert_eq!(Day2::part1(&reports, &wide).unwrap().to_string(), "2");
+        assert_eq!(part1(&reports), 0);
+        let forgiving = Params {
+            dampener: usize::MAX,
+            ..Params::default()
+        };
+        assert_eq!(Day2::part2(&reports, &forgiving).unwrap().to_string(), "2");
+    }
+
+    #[test]
+    fn agrees_with_trying_every_removal() {
+        let mut rng = Rng::new(2);
+        for _ in 0..300 {
+            let report = (0..rng.between(0, 7))
+                .map(|_| rng.between(0, 9) as i32)
+                .collect::<Vec<_>>();
+            for tolerance in 0..=2 {
+                let validator = Validator {
+                    steps: 1..=3,
+                    tolerance,
+                };
+                let fewest = (0..=tolerance.min(report.len())).find(|&k| {
+                    (0..report.len()).combinations(k).any(|removed| {
+                        let kept = (0..report.len())
+                            .filter(|i| !removed.contains(i))
+                            .map(|i| report[i])
+                            .collect::<Vec<_>>();
+                        validator
+                            .check(&kept)
+                            .is_some_and(|verdict| verdict.removed.is_empty())
+                    })
+                });
+                let verdict = validator.check(&report);
+                assert_eq!(verdict.map(|v| v.removed.len()), fewest, "{report:?}");
+            }
+        }
+    }
+}
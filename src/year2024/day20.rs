@@ -0,0 +1,251 @@
+use std::fmt::Display;
+
+use crate::{
+    generate::{maze, Generate, Rng},
+    grid::Grid,
+    params::params,
+    parse::ParseError,
+    point::Point,
+    search::{bfs, Graph},
+    solution::{Solution, SolveError, Variant},
+};
+use aoc_runner_derive::{aoc, aoc_generator};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub type Input = (Grid<u8>, Point, Point);
+
+#[aoc_generator(day20)]
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let grid = Grid::parse_only(input, b"#.SE")?;
+    let start = grid.require(input, b'S')?;
+    let end = grid.require(input, b'E')?;
+    Ok((grid, start, end))
+}
+
+struct RaceTrack<'a>(&'a Grid<u8>);
+
+impl Graph for RaceTrack<'_> {
+    type State = Point;
+
+    fn successors(&self, point: &Point) -> impl Iterator<Item = (Point, usize)> {
+        self.0
+            .neighbours(point)
+            .filter(|&p| self.0[p] != b'#')
+            .map(|p| (p, 1))
+    }
+}
+
+/// Every cell on the track, with its distance from the start.
+fn track(grid: &Grid<u8>, start: Point, end: Point) -> Vec<(Point, usize)> {
+    let distances = bfs(&RaceTrack(grid), [start], |&p| p == end).distances;
+    distances.into_iter().collect()
+}
+
+/// Cheats of at most `disable_collision_for` steps between `track[i]` and a cell after it in
+/// `track` that save at least `min_saving` picoseconds.
+fn cheats_from(
+    track: &[(Point, usize)],
+    i: usize,
+    disable_collision_for: usize,
+    min_saving: usize,
+) -> usize {
+    let (p, n) = track[i];
+    track[i + 1..]
+        .iter()
+        .filter(|(p2, n2)| {
+            let d = p.manhattan_distance(p2);
+            d <= disable_collision_for && n2.abs_diff(n) >= d.saturating_add(min_saving)
+        })
+        .count()
+}
+
+fn solve(
+    grid: &Grid<u8>,
+    start: Point,
+    end: Point,
+    disable_collision_for: usize,
+    min_saving: usize,
+) -> usize {
+    let track = track(grid, start, end);
+    (0..track.len())
+        .map(|i| cheats_from(&track, i, disable_collision_for, min_saving))
+        .sum()
+}
+
+#[cfg(feature = "parallel")]
+fn solve_parallel(
+    grid: &Grid<u8>,
+    start: Point,
+    end: Point,
+    disable_collision_for: usize,
+    min_saving: usize,
+) -> usize {
+    let track = track(grid, start, end);
+    (0..track.len())
+        .into_par_iter()
+        .map(|i| cheats_from(&track, i, disable_collision_for, min_saving))
+        .sum()
+}
+
+params! {
+    /// How many picoseconds a cheat must save to be counted.
+    pub struct Params {
+        min_saving: usize = 100,
+    }
+}
+
+fn part1_with(input: &Input, params: &Params) -> usize {
+    solve(&input.0, input.1, input.2, 2, params.min_saving)
+}
+
+fn part2_with(input: &Input, params: &Params) -> usize {
+    solve(&input.0, input.1, input.2, 20, params.min_saving)
+}
+
+#[aoc(day20, part1)]
+fn part1(input: &Input) -> usize {
+    part1_with(input, &Params::default())
+}
+
+#[aoc(day20, part2)]
+fn part2(input: &Input) -> usize {
+    part2_with(input, &Params::default())
+}
+
+#[cfg(feature = "parallel")]
+fn part1_parallel_with(input: &Input, params: &Params) -> usize {
+    solve_parallel(&input.0, input.1, input.2, 2, params.min_saving)
+}
+
+#[cfg(feature = "parallel")]
+fn part2_parallel_with(input: &Input, params: &Params) -> usize {
+    solve_parallel(&input.0, input.1, input.2, 20, params.min_saving)
+}
+
+#[cfg(feature = "parallel")]
+#[aoc(day20, part1, PARALLEL)]
+fn part1_parallel(input: &Input) -> usize {
+    part1_parallel_with(input, &Params::default())
+}
+
+#[cfg(feature = "parallel")]
+#[aoc(day20, part2, PARALLEL)]
+fn part2_parallel(input: &Input) -> usize {
+    part2_parallel_with(input, &Params::default())
+}
+
+/// Day 20: Race Condition.
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Input;
+    type Params = Params;
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 20;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part1_with(input, params))
+    }
+
+    fn part2(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part2_with(input, params))
+    }
+
+    fn variants() -> Vec<Variant<Self>> {
+        vec![
+            #[cfg(feature = "parallel")]
+            Variant {
+                part: 1,
+                name: "PARALLEL",
+                solve: |input, params| Ok(part1_parallel_with(input, params).to_string()),
+            },
+            #[cfg(feature = "parallel")]
+            Variant {
+                part: 2,
+                name: "PARALLEL",
+                solve: |input, params| Ok(part2_parallel_with(input, params).to_string()),
+            },
+        ]
+    }
+}
+
+params! {
+    /// Size of the generated racetrack.
+    pub struct Knobs {
+        size: usize = 141,
+    }
+}
+
+/// A single winding track through a wall, taken from a maze as the way from its bottom left
+/// corner to the spot furthest from there.
+impl Generate for Day20 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let maze = maze(rng, knobs.size, 0.0);
+        let start = Point::new(1, maze.height as isize - 2);
+        let search = bfs(&RaceTrack(&maze), [start], |_| false);
+        let (&end, _) = search
+            .distances
+            .iter()
+            .max_by_key(|&(p, d)| (d, p))
+            .unwrap();
+        let mut racetrack = maze.same_size_with(b'#');
+        for point in search.path_to(&end).unwrap() {
+            racetrack[point] = b'.';
+        }
+        racetrack[start] = b'S';
+        racetrack[end] = b'E';
+        racetrack.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############"#;
+
+    #[test]
+    fn part1_example() {
+        let params = Params { min_saving: 20 };
+        assert_eq!(part1_with(&parse(EXAMPLE).unwrap(), &params), 5);
+        let never = Params {
+            min_saving: usize::MAX,
+        };
+        assert_eq!(part1_with(&parse(EXAMPLE).unwrap(), &never), 0);
+    }
+
+    #[test]
+    fn part2_example() {
+        let params = Params { min_saving: 50 };
+        assert_eq!(part2_with(&parse(EXAMPLE).unwrap(), &params), 285);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_serial() {
+        let params = Params { min_saving: 50 };
+        assert_eq!(part2_parallel_with(&parse(EXAMPLE).unwrap(), &params), 285);
+    }
+}
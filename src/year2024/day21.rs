@@ -0,0 +1,423 @@
+use std::fmt::Display;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+use serde_json::json;
+
+use crate::{
+    explain::{Explain, Explanation},
+    generate::{Generate, Rng},
+    params::params,
+    parse::{self, number, ParseError},
+    solution::{Solution, SolveError},
+};
+
+#[derive(Debug, Clone, Copy)]
+enum Key {
+    Up = 0,
+    Activate = 1,
+    Left = 2,
+    Down = 3,
+    Right = 4,
+}
+
+fn get_paths<const HOLE_Y: usize>(
+    paths: &mut Vec<Vec<Key>>,
+    direction_key_positions: &[[usize; 2]],
+    start: usize,
+    end: usize,
+) {
+    let [start_x, start_y] = direction_key_positions[start];
+    let [end_x, end_y] = direction_key_positions[end];
+
+    if !(start_x == 0 && end_y == HOLE_Y) {
+        // Start by going vertically and then horizontally
+        // This must not be done if we start on the left button and go to the top row, as that would make us pass
+        // over an empty space.
+        let mut path = Vec::new();
+        if start_y < end_y {
+            path.extend((start_y..end_y).map(|_| Key::Down));
+        } else if start_y > end_y {
+            path.extend((end_y..start_y).map(|_| Key::Up));
+        }
+        if start_x < end_x {
+            path.extend((start_x..end_x).map(|_| Key::Right));
+        } else if start_x > end_x {
+            path.extend((end_x..start_x).map(|_| Key::Left));
+        }
+        // We always need to end with Activate, so we actually press the button we go to.
+        path.push(Key::Activate);
+        paths.push(path);
+    }
+
+    if start_x != end_x && start_y != end_y && !(start_y == HOLE_Y && end_x == 0) {
+        // If we need to both vertically and horizontally, we can also do it by going horizontally first.
+        // This must not be done if we end on the left button, as that would make us pass over an empty space.
+        let mut path = Vec::new();
+        if start_x < end_x {
+            path.extend((start_x..end_x).map(|_| Key::Right));
+        } else if start_x > end_x {
+            path.extend((end_x..start_x).map(|_| Key::Left));
+        }
+        if start_y < end_y {
+            path.extend((start_y..end_y).map(|_| Key::Down));
+        } else if start_y > end_y {
+            path.extend((end_y..start_y).map(|_| Key::Up));
+        }
+        // We always need to end with Activate, so we actually press the button we go to.
+        path.push(Key::Activate);
+        paths.push(path);
+    }
+
+    // It is never worth zigzagging, as such paths can be reduced into a non-zigzagging path just by duplicating
+    // some presses while eliminating others, to get a path that takes fewer presses in total.
+}
+
+const DIRECTIONAL: [[usize; 2]; 5] = [[1, 0], [2, 0], [0, 1], [1, 1], [2, 1]];
+
+/// Positions of `0` to `9` followed by `A`.
+const NUMERIC: [[usize; 2]; 11] = [
+    [1, 3],
+    [0, 2],
+    [1, 2],
+    [2, 2],
+    [0, 1],
+    [1, 1],
+    [2, 1],
+    [0, 0],
+    [1, 0],
+    [2, 0],
+    [2, 3],
+];
+
+/// What it costs to press every key of `path` in turn, starting from Activate, when going from
+/// key `a` to key `b` and pressing it costs `costs[a * 5 + b]`, or `None` if that doesn't fit.
+fn path_cost(path: &[Key], costs: &[usize]) -> Option<usize> {
+    let mut pos = Key::Activate;
+    path.iter().try_fold(0usize, |total, &new_pos| {
+        let cost = costs[pos as usize * 5 + new_pos as usize];
+        pos = new_pos;
+        total.checked_add(cost)
+    })
+}
+
+/// The cheapest of `paths`, leaving out any whose cost doesn't fit.
+fn cheapest(paths: &[Vec<Key>], costs: &[usize]) -> Option<usize> {
+    paths.iter().filter_map(|path| path_cost(path, costs)).min()
+}
+
+fn calc_level_costs(previous_costs: &[usize], paths: &[Vec<Vec<Key>>]) -> Option<Vec<usize>> {
+    paths
+        .iter()
+        // Sum up the costs of going from each button to the next one and pressing it, starting from Activate
+        .map(|paths| cheapest(paths, previous_costs))
+        .collect()
+}
+
+/// The cost of each move on the first of `size` directional keypads, which has to be at least
+/// one: the one you type on.
+fn calc_key_costs(size: usize) -> Result<Vec<usize>, SolveError> {
+    if size == 0 {
+        return Err(SolveError::new(
+            "there has to be at least one directional keypad",
+        ));
+    }
+    let paths: Vec<_> = (0..25)
+        .map(|i| {
+            let mut paths = Vec::new();
+            let start = i / 5;
+            let end = i % 5;
+            get_paths::<0>(&mut paths, &DIRECTIONAL, start, end);
+            paths
+        })
+        .collect();
+    let mut path_costs = paths
+        .iter()
+        .map(|paths| paths.iter().map(|path| path.len()).min().unwrap())
+        .collect::<Vec<usize>>();
+    for _ in 1..size {
+        path_costs = calc_level_costs(&path_costs, &paths).ok_or_else(too_many)?;
+    }
+    Ok(path_costs)
+}
+
+fn numeric_keys(code: &str) -> impl Iterator<Item = usize> + '_ {
+    code.chars().map(|key| match key {
+        '0'..='9' => key as usize - '0' as usize,
+        'A' => 10,
+        _ => unreachable!("codes are checked when parsing"),
+    })
+}
+
+/// The door codes, one per line, each some digits followed by `A`.
+#[aoc_generator(day21)]
+fn parse(input: &str) -> Result<String, ParseError> {
+    let codes = parse::lines(input)
+        .map(|code| match code.strip_suffix('A') {
+            Some(digits) if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) => {
+                number::<usize>(input, digits)?;
+                Ok(code)
+            }
+            _ => Err(ParseError::at(
+                input,
+                code,
+                "expected a door code such as `029A`",
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(codes.join("\n"))
+}
+
+/// The numeric part of a code such as `029A`.
+fn code_number(code: &str) -> usize {
+    code.split('A').next().unwrap().parse().unwrap()
+}
+
+fn too_many() -> SolveError {
+    SolveError::new("the number of presses doesn't fit in 64 bits")
+}
+
+/// How many presses you need to type `code`, given the cost of each move on the first
+/// directional keypad.
+fn presses(code: &str, key_costs: &[usize]) -> Result<usize, SolveError> {
+    let mut paths = Vec::new();
+    let mut pos = NUMERIC.len() - 1;
+    numeric_keys(code).try_fold(0usize, |total, new_pos| {
+        get_paths::<3>(&mut paths, &NUMERIC, pos, new_pos);
+        let cost = cheapest(&paths, key_costs);
+        pos = new_pos;
+        paths.clear();
+        total
+            .checked_add(cost.ok_or_else(too_many)?)
+            .ok_or_else(too_many)
+    })
+}
+
+/// The presses for `code` times its numeric part.
+fn complexity(code: &str, key_costs: &[usize]) -> Result<usize, SolveError> {
+    let presses = presses(code, key_costs)?;
+    presses
+        .checked_mul(code_number(code))
+        .ok_or_else(|| SolveError::new(format!("the complexity of {code} doesn't fit in 64 bits")))
+}
+
+fn solve(input: &str, keypads: usize) -> Result<usize, SolveError> {
+    let key_costs = calc_key_costs(keypads)?;
+    input.lines().try_fold(0usize, |total, code| {
+        let complexity = complexity(code, &key_costs)?;
+        total
+            .checked_add(complexity)
+            .ok_or_else(|| SolveError::new("the sum of the complexities doesn't fit in 64 bits"))
+    })
+}
+
+/// The directional keys that type `keys` on the keypad described by `positions`, picking the
+/// cheapest path between each pair according to `costs`.
+fn expand<const HOLE_Y: usize>(
+    keys: impl IntoIterator<Item = usize>,
+    positions: &[[usize; 2]],
+    start: usize,
+    costs: &[usize],
+) -> Vec<Key> {
+    let mut typed = Vec::new();
+    let mut paths = Vec::new();
+    let mut pos = start;
+    for key in keys {
+        get_paths::<HOLE_Y>(&mut paths, positions, pos, key);
+        let cost = |path: &&Vec<Key>| path_cost(path, costs).unwrap_or(usize::MAX);
+        typed.extend(paths.iter().min_by_key(cost).unwrap());
+        paths.clear();
+        pos = key;
+    }
+    typed
+}
+
+params! {
+    /// How many directional keypads sit between you and the door in each part.
+    pub struct Params {
+        part1_keypads: usize = 2,
+        part2_keypads: usize = 25,
+    }
+}
+
+fn part1_with(input: &str, params: &Params) -> Result<usize, SolveError> {
+    solve(input, params.part1_keypads)
+}
+
+fn part2_with(input: &str, params: &Params) -> Result<usize, SolveError> {
+    solve(input, params.part2_keypads)
+}
+
+#[aoc(day21, part1)]
+fn part1(input: &str) -> Result<usize, SolveError> {
+    part1_with(input, &Params::default())
+}
+
+#[aoc(day21, part2)]
+fn part2(input: &str) -> Result<usize, SolveError> {
+    part2_with(input, &Params::default())
+}
+
+/// Day 21: Keypad Conundrum.
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = String;
+    type Params = Params;
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 21;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        part1_with(input, params)
+    }
+
+    fn part2(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        part2_with(input, params)
+    }
+}
+
+/// Spells out what gets typed on each keypad, from the one at the door outwards. Expansion stops
+/// once a keypad would need more than `MAX_SHOWN` presses, which part 2 reaches quickly.
+impl Explain for Day21 {
+    fn explain(input: &String, params: &Params, part: u8) -> Explanation {
+        const MAX_SHOWN: usize = 1000;
+        let keypads = if part == 1 {
+            params.part1_keypads
+        } else {
+            params.part2_keypads
+        };
+        if let Err(e) = calc_key_costs(keypads) {
+            return Explanation::new("none", json!({ "keypads": keypads, "error": e.message }));
+        }
+        // costs[n] is the price of each move with n keypads between it and you. These all fit,
+        // as the costs for every keypad do.
+        let costs = std::iter::once(vec![1; 25])
+            .chain((1..=keypads).filter_map(|n| calc_key_costs(n).ok()))
+            .collect_vec();
+        let spell = |keys: &[Key]| {
+            keys.iter()
+                .map(|&k| ['^', 'A', '<', 'v', '>'][k as usize])
+                .collect::<String>()
+        };
+        let codes = input
+            .lines()
+            .map(|code| {
+                let presses = presses(code, &costs[keypads]).ok();
+                let mut typed = expand::<3>(numeric_keys(code), &NUMERIC, 10, &costs[keypads]);
+                let mut layers = vec![spell(&typed)];
+                for level in (0..keypads).rev() {
+                    if typed.len() > MAX_SHOWN {
+                        break;
+                    }
+                    typed = expand::<0>(
+                        typed.iter().map(|&k| k as usize),
+                        &DIRECTIONAL,
+                        Key::Activate as usize,
+                        &costs[level],
+                    );
+                    layers.push(spell(&typed));
+                }
+                json!({
+                    "code": code,
+                    "presses": presses,
+                    "complexity": complexity(code, &costs[keypads]).ok(),
+                    "unexpanded": keypads + 1 - layers.len(),
+                    "keypads": layers,
+                })
+            })
+            .collect_vec();
+        let answer = solve(input, keypads).map_or_else(|_| "none".to_string(), |a| a.to_string());
+        Explanation::new(answer, json!({ "keypads": keypads, "codes": codes }))
+    }
+}
+
+params! {
+    /// How many door codes to generate.
+    pub struct Knobs {
+        codes: usize = 5,
+    }
+}
+
+/// Door codes of three digits and an `A`.
+impl Generate for Day21 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        (0..knobs.codes)
+            .map(|_| format!("{:03}A", rng.below(1000)))
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explains_the_key_sequences() {
+        let input = "029A\n980A\n179A\n456A\n379A".to_string();
+        let explanation = Day21::explain(&input, &Params::default(), 1);
+        assert_eq!(explanation.answer, "126384");
+        let code = &explanation.witness["codes"][0];
+        assert_eq!(code["presses"], 68);
+        let keypads = code["keypads"].as_array().unwrap();
+        assert_eq!(keypads[0].as_str().unwrap().len(), 12);
+        assert_eq!(keypads[2].as_str().unwrap().len(), 68);
+        let part2 = Day21::explain(&input, &Params::default(), 2);
+        assert!(part2.witness["codes"][0]["unexpanded"].as_u64().unwrap() > 0);
+    }
+
+    #[test]
+    fn huge_codes_are_an_error() {
+        let input = parse("18446744073709551615A").unwrap();
+        assert_eq!(
+            part1(&input).unwrap_err().message,
+            "the complexity of 18446744073709551615A doesn't fit in 64 bits"
+        );
+        let input = parse("999999999999999999A\n999999999999999999A").unwrap();
+        assert!(part1(&input).is_err());
+        let explanation = Day21::explain(&input, &Params::default(), 1);
+        assert_eq!(explanation.answer, "none");
+        assert_eq!(explanation.witness["codes"][0]["complexity"], json!(null));
+    }
+
+    #[test]
+    fn needs_a_keypad_and_costs_that_fit() {
+        let input = parse("029A").unwrap();
+        let none = Params {
+            part1_keypads: 0,
+            ..Params::default()
+        };
+        let error = Day21::part1(&input, &none).err().unwrap();
+        assert_eq!(
+            error.message,
+            "there has to be at least one directional keypad"
+        );
+        assert_eq!(Day21::explain(&input, &none, 1).answer, "none");
+        let many = Params {
+            part2_keypads: 100,
+            ..Params::default()
+        };
+        assert!(Day21::part2(&input, &many).is_err());
+        assert_eq!(Day21::explain(&input, &many, 2).answer, "none");
+        let one = Params {
+            part1_keypads: 1,
+            ..Params::default()
+        };
+        // `v<<A>>^A<A>AvA<^AA>A<vAAA>^A` types `<A^A>^^AvvvA` on the keypad by the door.
+        assert_eq!(Day21::part1(&input, &one).unwrap().to_string(), "812");
+    }
+
+    #[test]
+    fn rejects_codes_off_the_keypad() {
+        assert_eq!(parse(" 029A\n980A \n").unwrap(), "029A\n980A");
+        for input in ["029A\n98B0A", "A", "029", "0A9A", "99999999999999999999A"] {
+            assert!(parse(input).is_err(), "{input}");
+        }
+    }
+}
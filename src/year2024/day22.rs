@@ -0,0 +1,342 @@
+use std::{fmt::Display, iter::successors};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde_json::json;
+
+use crate::{
+    explain::{Explain, Explanation},
+    generate::{Generate, Rng},
+    params::params,
+    parse::{self, number, ParseError},
+    solution::{Solution, SolveError, Variant},
+    stream::Stream,
+};
+/// Secrets are pruned below this after every step.
+const MODULUS: usize = 16777216;
+
+/// Initial secrets are below [`MODULUS`] like every later one, which keeps the multiplications
+/// in [`evolve_one`] far from overflowing.
+#[aoc_generator(day22)]
+fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    parse::lines(input)
+        .map(|l| match number(input, l)? {
+            secret if secret < MODULUS => Ok(secret),
+            _ => Err(ParseError::at(
+                input,
+                l,
+                format!("expected a secret below {MODULUS}"),
+            )),
+        })
+        .collect()
+}
+
+pub fn mix(secret: usize, other: usize) -> usize {
+    secret ^ other
+}
+
+pub fn prune(secret: usize) -> usize {
+    secret % MODULUS
+}
+
+pub fn mul_64(secret: usize) -> usize {
+    prune(mix(secret, secret * 64))
+}
+
+pub fn div_32(secret: usize) -> usize {
+    prune(mix(secret, secret / 32))
+}
+
+pub fn mul_2048(secret: usize) -> usize {
+    prune(mix(secret, secret * 2048))
+}
+
+pub fn evolve(secret: usize) -> std::iter::Successors<usize, impl FnMut(&usize) -> Option<usize>> {
+    successors(Some(secret), |previous| Some(evolve_one(*previous)))
+}
+
+pub fn evolve_one(secret: usize) -> usize {
+    mul_2048(div_32(mul_64(secret)))
+}
+
+#[aoc(day22, part1)]
+fn part1(input: &[usize]) -> usize {
+    input
+        .iter()
+        .map(|initial| evolve(*initial).nth(2000).unwrap())
+        .sum()
+}
+
+#[cfg(feature = "parallel")]
+#[aoc(day22, part1, PARALLEL)]
+fn part1_parallel(input: &[usize]) -> usize {
+    input
+        .par_iter()
+        .map(|initial| evolve(*initial).nth(2000).unwrap())
+        .sum()
+}
+
+/// Every run of four price changes, each in -9..=9, as an index below 19^4.
+const SEQUENCES: usize = 130321;
+
+/// Every run of four price changes in a buyer's first 2000 secrets, as an index below
+/// [`SEQUENCES`], with the price the buyer offers right after it.
+fn price_changes(initial: usize) -> impl Iterator<Item = (usize, usize)> {
+    let to_index = |previous: usize, current: usize| 9 + current % 10 - previous % 10;
+    let first = evolve_one(initial);
+    let second = evolve_one(first);
+    let third = evolve_one(second);
+
+    let mut b = to_index(initial, first);
+    let mut c = to_index(first, second);
+    let mut d = to_index(second, third);
+    let mut number = third;
+    (3..2000).map(move |_| {
+        let previous = number;
+        number = evolve_one(number);
+        let a;
+        (a, b, c, d) = (b, c, d, to_index(previous, number));
+        (6859 * a + 361 * b + 19 * c + d, number % 10)
+    })
+}
+
+/// The four price changes behind an index from [`price_changes`].
+fn changes_of(sequence: usize) -> [isize; 4] {
+    [6859, 361, 19, 1].map(|place| (sequence / place % 19) as isize - 9)
+}
+
+/// Adds what buyer `id` pays the first time each sequence of changes appears to `result`.
+/// `seen` remembers the last buyer that paid out on each sequence.
+fn add_first_prices(id: usize, initial: usize, result: &mut [usize], seen: &mut [usize]) {
+    for (key, price) in price_changes(initial) {
+        if seen[key] != id {
+            result[key] += price;
+            seen[key] = id;
+        }
+    }
+}
+
+#[aoc(day22, part2)]
+fn part2(input: &[usize]) -> usize {
+    let mut result = vec![0; SEQUENCES];
+    let mut seen = vec![usize::MAX; SEQUENCES];
+    for (id, &initial) in input.iter().enumerate() {
+        add_first_prices(id, initial, &mut result, &mut seen);
+    }
+    *result.iter().max().unwrap()
+}
+
+#[cfg(feature = "parallel")]
+#[aoc(day22, part2, PARALLEL)]
+fn part2_parallel(input: &[usize]) -> usize {
+    let result = input
+        .par_iter()
+        .enumerate()
+        .fold(
+            || (vec![0; SEQUENCES], vec![usize::MAX; SEQUENCES]),
+            |(mut result, mut seen), (id, &initial)| {
+                add_first_prices(id, initial, &mut result, &mut seen);
+                (result, seen)
+            },
+        )
+        .map(|(result, _)| result)
+        .reduce(
+            || vec![0; SEQUENCES],
+            |mut total, result| {
+                total.iter_mut().zip(result).for_each(|(t, r)| *t += r);
+                total
+            },
+        );
+    *result.iter().max().unwrap()
+}
+
+/// Day 22: Monkey Market.
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Vec<usize>;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 22;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part2(input))
+    }
+
+    fn variants() -> Vec<Variant<Self>> {
+        vec![
+            #[cfg(feature = "parallel")]
+            Variant {
+                part: 1,
+                name: "PARALLEL",
+                solve: |input, _| Ok(part1_parallel(input).to_string()),
+            },
+            #[cfg(feature = "parallel")]
+            Variant {
+                part: 2,
+                name: "PARALLEL",
+                solve: |input, _| Ok(part2_parallel(input).to_string()),
+            },
+        ]
+    }
+}
+
+/// What streaming remembers of the buyers seen so far: the sum of their 2000th secrets and, per
+/// run of changes, the bananas it would have bought.
+#[derive(Debug, Default)]
+pub struct Market {
+    buyers: usize,
+    secrets: usize,
+    bananas: Vec<usize>,
+    seen: Vec<usize>,
+}
+
+impl Stream for Day22 {
+    type State = Market;
+
+    fn fold(market: &mut Market, record: &str, _: &()) -> Result<(), ParseError> {
+        if market.bananas.is_empty() {
+            market.bananas = vec![0; SEQUENCES];
+            market.seen = vec![usize::MAX; SEQUENCES];
+        }
+        for initial in parse(record)? {
+            market.secrets += evolve(initial).nth(2000).unwrap();
+            add_first_prices(
+                market.buyers,
+                initial,
+                &mut market.bananas,
+                &mut market.seen,
+            );
+            market.buyers += 1;
+        }
+        Ok(())
+    }
+
+    fn answer(market: &Market, _: &(), part: u8) -> Result<String, SolveError> {
+        Ok(match part {
+            1 => market.secrets,
+            _ => market.bananas.iter().max().copied().unwrap_or(0),
+        }
+        .to_string())
+    }
+}
+
+/// Part 1 lists each buyer's 2000th secret. Part 2 names the best run of price changes and what
+/// every buyer pays for it, or `none` for buyers whose prices never change that way.
+impl Explain for Day22 {
+    fn explain(input: &Vec<usize>, _: &(), part: u8) -> Explanation {
+        if part == 1 {
+            let buyers = input
+                .iter()
+                .map(|&initial| json!({"initial": initial, "secret": evolve(initial).nth(2000)}))
+                .collect::<Vec<_>>();
+            return Explanation::new(part1(input), json!({ "buyers": buyers }));
+        }
+        let mut result = vec![0; SEQUENCES];
+        let mut seen = vec![usize::MAX; SEQUENCES];
+        for (id, &initial) in input.iter().enumerate() {
+            add_first_prices(id, initial, &mut result, &mut seen);
+        }
+        let (best, &bananas) = result
+            .iter()
+            .enumerate()
+            .max_by_key(|&(sequence, bananas)| (bananas, std::cmp::Reverse(sequence)))
+            .unwrap();
+        let buyers = input
+            .iter()
+            .map(|&initial| {
+                let price = price_changes(initial).find(|&(sequence, _)| sequence == best);
+                json!({"initial": initial, "price": price.map(|(_, price)| price)})
+            })
+            .collect::<Vec<_>>();
+        Explanation::new(
+            bananas,
+            json!({"changes": changes_of(best), "bananas": bananas, "buyers": buyers}),
+        )
+    }
+}
+
+params! {
+    /// How many buyers to generate.
+    pub struct Knobs {
+        buyers: usize = 1600,
+    }
+}
+
+/// Buyers with random initial secrets, all below the pruning modulus.
+impl Generate for Day22 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        (0..knobs.buyers)
+            .map(|_| (rng.below(MODULUS - 1) + 1).to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    pub const EXAMPLE: &str = r#"1
+    10
+    100
+    2024"#;
+
+    #[test]
+    fn part1_ops() {
+        let next_numbers = evolve(123).take(6).collect_vec();
+        assert_eq!(
+            next_numbers,
+            [123, 15887950, 16495136, 527345, 704524, 1553684]
+        )
+    }
+
+    #[test]
+    pub fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 37327623);
+    }
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 24);
+    }
+
+    #[test]
+    fn rejects_secrets_that_would_overflow() {
+        let error = parse("1\n18446744073709551615").unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "expected a secret below 16777216")
+        );
+        assert!(parse("16777215").is_ok());
+    }
+
+    #[test]
+    fn explains_the_best_changes() {
+        let explanation = Day22::explain(&parse("1\n2\n3\n2024").unwrap(), &(), 2);
+        assert_eq!(explanation.answer, "23");
+        assert_eq!(explanation.witness["changes"], json!([-2, 1, -1, 3]));
+        assert_eq!(explanation.witness["buyers"][2]["price"], json!(null));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_serial() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1_parallel(&input), part1(&input));
+        assert_eq!(part2_parallel(&input), part2(&input));
+    }
+}
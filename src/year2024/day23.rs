@@ -1,9 +1,18 @@
-use std::collections::BTreeSet;
+use std::{collections::BTreeSet, fmt::Display};
 
 use ahash::{HashMap, HashSet};
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
 
+use crate::{
+    generate::{Generate, Rng},
+    metrics,
+    params::params,
+    parse::{self, ParseError},
+    solution::{Solution, SolveError},
+    stream::Stream,
+};
+
 #[derive(Debug, Clone)]
 struct Node {
     name: String,
@@ -11,7 +20,7 @@ struct Node {
 }
 
 #[derive(Debug, Default)]
-struct Graph {
+pub struct Graph {
     nodes: Vec<Node>,
     seen: HashMap<String, usize>,
 }
@@ -33,8 +42,10 @@ impl Graph {
     fn add_edge(&mut self, a: &str, b: &str) {
         let a = self.add_node(a);
         let b = self.add_node(b);
-        self.nodes[a].edges.push(b);
-        self.nodes[b].edges.push(a);
+        if !self.nodes[a].edges.contains(&b) {
+            self.nodes[a].edges.push(b);
+            self.nodes[b].edges.push(a);
+        }
     }
 }
 type Cycle = BTreeSet<usize>;
@@ -72,6 +83,10 @@ fn find_max_clique(graph: &Graph) -> HashSet<usize> {
         x: &mut HashSet<usize>,
         max_clique: &mut HashSet<usize>,
     ) {
+        metrics::record(|m| {
+            m.nodes_expanded += 1;
+            m.reach(r.len());
+        });
         if p.is_empty() && x.is_empty() {
             if r.len() > max_clique.len() {
                 max_clique.clear();
@@ -121,13 +136,19 @@ fn find_max_clique(graph: &Graph) -> HashSet<usize> {
 }
 
 #[aoc_generator(day23)]
-fn parse(input: &str) -> Graph {
+fn parse(input: &str) -> Result<Graph, ParseError> {
     let mut g = Graph::default();
-    for line in input.lines() {
-        let (a, b) = line.trim().split_once('-').unwrap();
+    connect(&mut g, input)?;
+    Ok(g)
+}
+
+/// Adds the connections listed in `input` to `g`.
+fn connect(g: &mut Graph, input: &str) -> Result<(), ParseError> {
+    for line in parse::lines(input) {
+        let (a, b) = parse::split_once(input, line, "-")?;
         g.add_edge(a, b);
     }
-    g
+    Ok(())
 }
 
 #[aoc(day23, part1)]
@@ -151,6 +172,99 @@ fn part2(graph: &Graph) -> String {
         .join(",")
 }
 
+/// Day 23: LAN Party.
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Graph;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 23;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part2(input))
+    }
+}
+
+/// Builds the network a connection at a time. Repeated connections are only kept once, so the
+/// state grows with the computers and distinct connections rather than with the input.
+impl Stream for Day23 {
+    type State = Graph;
+
+    fn fold(graph: &mut Graph, record: &str, _: &()) -> Result<(), ParseError> {
+        connect(graph, record)
+    }
+
+    fn answer(graph: &Graph, _: &(), part: u8) -> Result<String, SolveError> {
+        Ok(match part {
+            1 => part1(graph).to_string(),
+            _ => part2(graph),
+        })
+    }
+}
+
+params! {
+    /// Size of the generated network and of the LAN party planted in it.
+    pub struct Knobs {
+        /// How many computers, at most 676 as names are two letters.
+        computers: usize = 520,
+        /// Size of the planted LAN party.
+        clique: usize = 13,
+        /// Connections most computers end up with.
+        degree: usize = 13,
+    }
+}
+
+/// A sparse random network with one LAN party planted in it. Random connections rarely close
+/// even a triangle, so with the defaults the planted party is the largest by far.
+impl Generate for Day23 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let mut names = (b'a'..=b'z')
+            .flat_map(|a| (b'a'..=b'z').map(move |b| format!("{}{}", a as char, b as char)))
+            .collect_vec();
+        rng.shuffle(&mut names);
+        names.truncate(knobs.computers.max(2));
+        let n = names.len();
+        let mut connected = HashSet::default();
+        let mut degrees = vec![0; n];
+        let mut connect = |a: usize, b: usize, degrees: &mut Vec<usize>| {
+            if a != b && connected.insert((a.min(b), a.max(b))) {
+                degrees[a] += 1;
+                degrees[b] += 1;
+            }
+        };
+        for a in 0..knobs.clique.min(n) {
+            for b in a + 1..knobs.clique.min(n) {
+                connect(a, b, &mut degrees);
+            }
+        }
+        for _ in 0..n * knobs.degree * 4 {
+            let (a, b) = (rng.below(n), rng.below(n));
+            if degrees[a] < knobs.degree && degrees[b] < knobs.degree {
+                connect(a, b, &mut degrees);
+            }
+        }
+        let mut lines = connected
+            .iter()
+            .sorted()
+            .map(|&(a, b)| format!("{}-{}", names[a], names[b]))
+            .collect_vec();
+        rng.shuffle(&mut lines);
+        lines.join("\n")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,6 +304,17 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse(EXAMPLE)), 7);
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 7);
+    }
+
+    #[test]
+    fn repeated_connections_are_kept_once() {
+        let twice = format!("{EXAMPLE}\n{EXAMPLE}\nvc-tb");
+        let graph = parse(&twice).unwrap();
+        let once = parse(EXAMPLE).unwrap();
+        let degrees = |g: &Graph| g.nodes.iter().map(|n| n.edges.len()).sum::<usize>();
+        assert_eq!(degrees(&graph), degrees(&once));
+        assert_eq!(part1(&graph), 7);
+        assert_eq!(part2(&graph), part2(&once));
     }
 }
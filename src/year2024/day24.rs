@@ -0,0 +1,580 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt::Display,
+};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, char, one_of, space1},
+    combinator::{map, value},
+    error::context,
+    sequence::{separated_pair, tuple},
+};
+
+use crate::{
+    combinators::{finish, lines, two_sections, ws, Res},
+    generate::{Generate, Rng},
+    params::params,
+    parse::ParseError,
+    solution::{Solution, SolveError},
+};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Operator {
+    And,
+    Or,
+    Xor,
+}
+
+impl Operator {
+    fn evaluate(&self, left: bool, right: bool) -> bool {
+        match self {
+            Operator::And => left && right,
+            Operator::Or => left || right,
+            Operator::Xor => left ^ right,
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Operator::And => "AND",
+                Operator::Or => "OR",
+                Operator::Xor => "XOR",
+            }
+        )
+    }
+}
+
+#[derive(Eq, PartialEq, Debug)]
+struct Expression<'a> {
+    left: &'a str,
+    operator: Operator,
+    right: &'a str,
+    output: &'a str,
+    evaluated: bool,
+}
+
+impl<'a> Expression<'a> {
+    fn new(left: &'a str, operator: Operator, right: &'a str, output: &'a str) -> Self {
+        let (left, right) = if left < right {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        Expression {
+            left,
+            operator,
+            right,
+            output,
+            evaluated: false,
+        }
+    }
+
+    fn evaluate(&mut self, store: &mut HashMap<&'a str, Option<bool>>) {
+        if !self.evaluated {
+            if let Some(left) = store[self.left] {
+                if let Some(right) = store[self.right] {
+                    if store[self.output].is_none() {
+                        store.insert(self.output, Some(self.operator.evaluate(left, right)));
+                    }
+                    self.evaluated = true;
+                }
+            }
+        }
+    }
+
+    fn is_input(&self, input: &str) -> bool {
+        self.left == input || self.right == input
+    }
+
+    fn is_output(&self, output: &str) -> bool {
+        self.output == output
+    }
+}
+
+impl Display for Expression<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} -> {}",
+            self.left, self.operator, self.right, self.output
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Gate {
+    left: String,
+    operator: Operator,
+    right: String,
+    output: String,
+}
+
+impl Gate {
+    fn expression(&self) -> Expression<'_> {
+        Expression::new(&self.left, self.operator, &self.right, &self.output)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Circuit {
+    wires: Vec<(String, bool)>,
+    gates: Vec<Gate>,
+}
+
+fn wire(input: &str) -> Res<'_, String> {
+    context("a wire name", map(alphanumeric1, str::to_string))(input)
+}
+
+fn initial_value(input: &str) -> Res<'_, (String, bool)> {
+    separated_pair(wire, ws(char(':')), map(one_of("01"), |v| v == '1'))(input)
+}
+
+fn operator(input: &str) -> Res<'_, Operator> {
+    context(
+        "AND, OR or XOR",
+        alt((
+            value(Operator::And, tag("AND")),
+            value(Operator::Or, tag("OR")),
+            value(Operator::Xor, tag("XOR")),
+        )),
+    )(input)
+}
+
+fn gate(input: &str) -> Res<'_, Gate> {
+    map(
+        tuple((wire, space1, operator, space1, wire, ws(tag("->")), wire)),
+        |(left, _, operator, _, right, _, output)| Gate {
+            left,
+            operator,
+            right,
+            output,
+        },
+    )(input)
+}
+
+#[aoc_generator(day24)]
+fn parse(input: &str) -> Result<Circuit, ParseError> {
+    let (wires, gates) = finish(input, two_sections(lines(initial_value), lines(gate)))?;
+    Ok(Circuit { wires, gates })
+}
+
+// dumb works well enough
+#[aoc(day24, part1)]
+fn part1(input: &Circuit) -> Result<u64, SolveError> {
+    let mut store: HashMap<&str, Option<bool>> = HashMap::new();
+
+    for (wire, value) in &input.wires {
+        store.insert(wire, Some(*value));
+    }
+
+    let mut expressions: Vec<Expression> = input
+        .gates
+        .iter()
+        .map(|gate| {
+            let e = gate.expression();
+            store.entry(e.left).or_insert(None);
+            store.entry(e.right).or_insert(None);
+            store.entry(e.output).or_insert(None);
+
+            e
+        })
+        .collect();
+
+    while !expressions.is_empty() {
+        for e in &mut expressions {
+            e.evaluate(&mut store);
+        }
+        if expressions.iter().all(|e| !e.evaluated) {
+            // Nothing changed, so the rest wait on wires that never get a value, or on each other.
+            return Err(SolveError::new(format!(
+                "`{}` never gets both its inputs",
+                expressions[0]
+            )));
+        }
+        // should check instead whether all z stores are evaluated
+        expressions.retain(|e| !e.evaluated);
+
+        if store
+            .iter()
+            .filter(|(k, _)| k.starts_with('z'))
+            .all(|(_, v)| v.is_some())
+        {
+            break;
+        }
+    }
+
+    let mut zs: Vec<(&str, Option<bool>)> = store
+        .into_iter()
+        .filter(|(k, _)| k.starts_with('z'))
+        .collect();
+    zs.sort();
+    zs.reverse();
+
+    Ok(zs
+        .into_iter()
+        .fold(0, |acc, (_, v)| acc * 2 + if v.unwrap() { 1 } else { 0 }))
+}
+
+/// The first of `formulae` that `matches`, or an error saying the circuit has no `wanted` gate
+/// and so can't be an adder.
+fn find_gate<'e, 'a>(
+    formulae: &'e [Expression<'a>],
+    wanted: impl Display,
+    matches: impl Fn(&Expression) -> bool,
+) -> Result<&'e Expression<'a>, SolveError> {
+    formulae
+        .iter()
+        .find(|e| matches(e))
+        .ok_or_else(|| SolveError::new(format!("no gate computes {wanted}, so this is no adder")))
+}
+
+#[aoc(day24, part2)]
+fn part2(input: &Circuit) -> Result<String, SolveError> {
+    // each x_i and y_i, and nothing else
+    let bitlen = input.wires.len() as u8 / 2;
+
+    let formulae: Vec<Expression> = input.gates.iter().map(Gate::expression).collect();
+
+    let mut swapped = BTreeSet::new();
+    let z00 = find_gate(&formulae, "x00 XOR y00", |e| {
+        e.left == "x00" && e.right == "y00" && e.operator == Operator::Xor
+    })?;
+
+    if z00.output != "z00" {
+        swapped.insert(z00.output.to_string());
+    }
+
+    let mut carry: &str = find_gate(&formulae, "x00 AND y00", |e| {
+        e.left == "x00" && e.right == "y00" && e.operator == Operator::And
+    })?
+    .output;
+
+    for bit in 1..bitlen {
+        // find basic add x_bit XOR y_bit -> ??
+        let x = format!("x{bit:02}");
+        let y = format!("y{bit:02}");
+        let z = format!("z{bit:02}");
+        let basic_add = find_gate(&formulae, format!("{x} XOR {y}"), |e| {
+            e.left == x && e.right == y && e.operator == Operator::Xor
+        })?
+        .output;
+        // check Add (either previous carry, basic add or output can be wrong)
+
+        let add = find_gate(&formulae, format!("the sum for {z}"), |e| {
+            e.operator == Operator::Xor && (e.is_input(carry) || e.is_input(basic_add))
+        })?;
+        if !add.is_output(&z) {
+            swapped.insert(z);
+            swapped.insert(add.output.to_string());
+        }
+
+        if !add.is_input(basic_add) {
+            swapped.insert(basic_add.to_string());
+        }
+
+        if !add.is_input(carry) {
+            swapped.insert(carry.to_string());
+        }
+        // check basic carry - only output can be wrong
+        let basic_carry = find_gate(&formulae, format!("{x} AND {y}"), |e| {
+            e.left == x && e.right == y && e.operator == Operator::And
+        })?
+        .output;
+        // check cascade carry (if either previous carry or basic add were wrong, ignore that)
+        // if carry was wrong, basic_add could also be wrong... let's ignore that for now
+        let cascade_carry =
+            find_gate(&formulae, format!("the cascade carry for bit {bit}"), |e| {
+                e.operator == Operator::And && (e.is_input(basic_add) || e.is_input(carry))
+            })?;
+
+        if !cascade_carry.is_input(basic_add) {
+            swapped.insert(basic_add.to_string());
+        }
+
+        if !cascade_carry.is_input(carry) {
+            swapped.contains(carry);
+        }
+        // check carry (basic carry or cascade carry can be wrong)
+        let carry_gate = find_gate(&formulae, format!("the carry out of bit {bit}"), |e| {
+            e.operator == Operator::Or
+                && (e.is_input(cascade_carry.output) || e.is_input(basic_carry))
+        })?;
+
+        if !carry_gate.is_input(cascade_carry.output) {
+            swapped.insert(cascade_carry.output.to_string());
+        }
+
+        if !carry_gate.is_input(basic_carry) {
+            swapped.insert(basic_carry.to_string());
+        }
+
+        carry = carry_gate.output;
+    }
+
+    let swapped: Vec<_> = swapped.into_iter().collect();
+    Ok(swapped.join(","))
+}
+
+/// Day 24: Crossed Wires.
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Circuit;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 24;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        part2(input)
+    }
+}
+
+params! {
+    /// Size of the generated adder and how many of its outputs are mixed up.
+    pub struct Knobs {
+        /// Width of the numbers being added.
+        bits: usize = 45,
+        /// Pairs of gate outputs to swap, each pair in a different full adder.
+        swaps: usize = 4,
+    }
+}
+
+/// A ripple-carry adder with random wire names and a few pairs of outputs swapped. The swaps
+/// are of the kinds found in real inputs, always inside one full adder away from the first
+/// and last bits: the sum bit with the carry out, with either carry half, or the two half
+/// adder outputs with each other.
+impl Generate for Day24 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let bits = knobs.bits.clamp(2, 99);
+        let mut used = HashSet::new();
+        let mut name = || loop {
+            let first = (b'a' + rng.below(23) as u8) as char;
+            let rest: String = (0..2)
+                .map(|_| (b'a' + rng.below(26) as u8) as char)
+                .collect();
+            let name = format!("{first}{rest}");
+            if used.insert(name.clone()) {
+                return name;
+            }
+        };
+        // Per bit: half sum, half carry, sum, carry through and carry out.
+        let mut gates: Vec<(String, Operator, String, String)> = Vec::new();
+        let mut adders = Vec::new();
+        let mut carry = String::new();
+        for bit in 0..bits {
+            let (x, y, z) = (
+                format!("x{bit:02}"),
+                format!("y{bit:02}"),
+                format!("z{bit:02}"),
+            );
+            if bit == 0 {
+                carry = name();
+                gates.push((x.clone(), Operator::Xor, y.clone(), z));
+                gates.push((x, Operator::And, y, carry.clone()));
+                continue;
+            }
+            let (half_sum, half_carry, through) = (name(), name(), name());
+            let carry_out = if bit == bits - 1 {
+                format!("z{bits:02}")
+            } else {
+                name()
+            };
+            let first = gates.len();
+            gates.push((x.clone(), Operator::Xor, y.clone(), half_sum.clone()));
+            gates.push((x, Operator::And, y, half_carry.clone()));
+            gates.push((half_sum.clone(), Operator::Xor, carry.clone(), z));
+            gates.push((half_sum, Operator::And, carry, through.clone()));
+            gates.push((through, Operator::Or, half_carry, carry_out.clone()));
+            adders.push(first);
+            carry = carry_out;
+        }
+        // Swap in adders that aren't next to each other, leaving out the last.
+        let mut candidates = adders[..adders.len() - 1].to_vec();
+        rng.shuffle(&mut candidates);
+        let mut chosen: Vec<usize> = Vec::new();
+        for first in candidates {
+            if chosen.len() == knobs.swaps {
+                break;
+            }
+            if chosen.iter().all(|&other| other.abs_diff(first) > 5) {
+                chosen.push(first);
+            }
+        }
+        for first in chosen {
+            let (a, b) = *rng.pick(&[(0, 1), (2, 4), (2, 3), (2, 1)]);
+            let output = gates[first + a].3.clone();
+            gates[first + a].3 = std::mem::replace(&mut gates[first + b].3, output);
+        }
+        rng.shuffle(&mut gates);
+        let mut lines = Vec::new();
+        for input in ['x', 'y'] {
+            for bit in 0..bits {
+                lines.push(format!("{input}{bit:02}: {}", rng.below(2)));
+            }
+        }
+        lines.push(String::new());
+        for (left, operator, right, output) in gates {
+            let (left, right) = if rng.chance(0.5) {
+                (left, right)
+            } else {
+                (right, left)
+            };
+            lines.push(format!("{left} {operator} {right} -> {output}"));
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const TEST_INPUT_SMALL: &str = "x00: 1
+x01: 1
+x02: 1
+y00: 0
+y01: 1
+y02: 0
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+x02 OR y02 -> z02";
+
+    const TEST_INPUT_LARGE: &str = "x00: 1
+x01: 0
+x02: 1
+x03: 1
+x04: 0
+y00: 1
+y01: 1
+y02: 1
+y03: 1
+y04: 1
+
+ntg XOR fgs -> mjb
+y02 OR x01 -> tnw
+kwq OR kpj -> z05
+x00 OR x03 -> fst
+tgd XOR rvg -> z01
+vdt OR tnw -> bfw
+bfw AND frj -> z10
+ffh OR nrd -> bqk
+y00 AND y03 -> djm
+y03 OR y00 -> psh
+bqk OR frj -> z08
+tnw OR fst -> frj
+gnj AND tgd -> z11
+bfw XOR mjb -> z00
+x03 OR x00 -> vdt
+gnj AND wpb -> z02
+x04 AND y00 -> kjc
+djm OR pbm -> qhw
+nrd AND vdt -> hwm
+kjc AND fst -> rvg
+y04 OR y02 -> fgs
+y01 AND x02 -> pbm
+ntg OR kjc -> kwq
+psh XOR fgs -> tgd
+qhw XOR tgd -> z09
+pbm OR djm -> kpj
+x03 XOR y03 -> ffh
+x00 XOR y04 -> ntg
+bfw OR bqk -> z06
+nrd XOR fgs -> wpb
+frj XOR qhw -> z04
+bqk OR frj -> z07
+y03 OR x01 -> nrd
+hwm AND bqk -> z03
+tgd XOR rvg -> z12
+tnw OR pbm -> gnj";
+
+    #[test_case(TEST_INPUT_SMALL, 4; "small test input")]
+    #[test_case(TEST_INPUT_LARGE, 2024; "large test input")]
+    #[test_case(TEST_INPUT_PART_2, 9; "swapped and gates")]
+    fn test_part1(input: &str, output: u64) {
+        assert_eq!(output, part1(&parse(input).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn finds_the_swaps_in_generated_adders() {
+        let mut rng = Rng::new(24);
+        let knobs = Knobs { bits: 12, swaps: 0 };
+        let circuit = parse(&Day24::generate(&mut rng, &knobs)).unwrap();
+        let number = |name: char| {
+            circuit
+                .wires
+                .iter()
+                .filter(|(wire, _)| wire.starts_with(name))
+                .map(|(wire, on)| (*on as u64) << wire[1..].parse::<u64>().unwrap())
+                .sum::<u64>()
+        };
+        assert_eq!(part1(&circuit).unwrap(), number('x') + number('y'));
+        assert_eq!(part2(&circuit).unwrap(), "");
+        let swapped = parse(&Day24::generate(&mut rng, &Knobs { swaps: 3, ..knobs })).unwrap();
+        assert_eq!(part2(&swapped).unwrap().split(',').count(), 6);
+    }
+
+    #[test]
+    fn parses_indented_crlf_input() {
+        let input = "  x00: 1\r\n  y00: 1\r\n\r\n  x00 AND y00 -> z00\r\n";
+        assert_eq!(part1(&parse(input).unwrap()).unwrap(), 1);
+    }
+
+    #[test]
+    fn reports_circuits_that_never_settle() {
+        let circuit = parse("x00: 1\ny00: 0\n\nx00 AND w00 -> z00").unwrap();
+        assert_eq!(
+            part1(&circuit).unwrap_err().message,
+            "`w00 AND x00 -> z00` never gets both its inputs"
+        );
+        let error = part2(&circuit).unwrap_err();
+        assert!(error.message.contains("x00 XOR y00"), "{error}");
+    }
+
+    #[test]
+    fn reports_unknown_operators() {
+        let error = parse("x00: 1\ny00: 0\n\nx00 NAND y00 -> z00").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 5));
+        assert_eq!(error.message, "expected AND, OR or XOR");
+    }
+
+    const TEST_INPUT_PART_2: &str = "x00: 0
+x01: 1
+x02: 0
+x03: 1
+x04: 0
+x05: 1
+y00: 0
+y01: 0
+y02: 1
+y03: 1
+y04: 0
+y05: 1
+
+x00 AND y00 -> z05
+x01 AND y01 -> z02
+x02 AND y02 -> z01
+x03 AND y03 -> z03
+x04 AND y04 -> z04
+x05 AND y05 -> z00";
+}
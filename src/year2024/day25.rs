@@ -0,0 +1,176 @@
+use std::fmt::Display;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+
+use crate::{
+    generate::{Generate, Rng},
+    grid::Grid,
+    params::params,
+    parse::{self, ParseError},
+    solution::{Solution, SolveError},
+};
+
+pub struct Schematic {
+    pub is_key: bool,
+    pub heights: Vec<usize>,
+}
+
+impl Schematic {
+    fn fits(&self, other: &Schematic) -> bool {
+        (self.is_key ^ other.is_key)
+            && self
+                .heights
+                .iter()
+                .zip(&other.heights)
+                .all(|(&a, b)| a + b <= 7)
+    }
+}
+
+impl Schematic {
+    fn parse(input: &str, block: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse_only(block, b"#.").map_err(|e| e.rebase(input, block))?;
+        let is_key = grid.row(0)[0] == b'.';
+        let heights = grid
+            .columns()
+            .map(|column| column.filter(|&&c| c == b'#').count())
+            .collect_vec();
+        Ok(Self { is_key, heights })
+    }
+}
+
+#[aoc_generator(day25)]
+fn parse(input: &str) -> Result<Vec<Schematic>, ParseError> {
+    parse::sections(input)
+        .into_iter()
+        .map(|block| Schematic::parse(input, block))
+        .collect()
+}
+
+#[aoc(day25, part1)]
+fn part1(input: &[Schematic]) -> usize {
+    let (keys, locks): (Vec<_>, Vec<_>) = input.iter().partition(|s| s.is_key);
+
+    keys.iter()
+        .map(|key| locks.iter().filter(|lock| key.fits(lock)).count())
+        .sum()
+}
+
+#[aoc(day25, part2)]
+fn part2(_input: &[Schematic]) -> usize {
+    // There is no second puzzle on the last day, the star is awarded for finishing the others.
+    0
+}
+
+/// Day 25: Code Chronicle.
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Vec<Schematic>;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 25;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part2(input))
+    }
+}
+
+params! {
+    /// How many locks and keys to generate.
+    pub struct Knobs {
+        locks: usize = 250,
+        keys: usize = 250,
+    }
+}
+
+/// Locks and keys with pins and cuts of random heights, in random order.
+impl Generate for Day25 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let mut schematics = (0..knobs.locks + knobs.keys)
+            .map(|i| {
+                let heights = (0..5).map(|_| rng.below(6)).collect_vec();
+                let rows = (0..7).map(|row| {
+                    heights
+                        .iter()
+                        .map(|&h| if row <= h { '#' } else { '.' })
+                        .collect::<String>()
+                });
+                let rows = rows.collect_vec();
+                if i < knobs.locks {
+                    rows.join("\n")
+                } else {
+                    rows.iter().rev().join("\n")
+                }
+            })
+            .collect_vec();
+        rng.shuffle(&mut schematics);
+        schematics.join("\n\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str = r#"#####
+    .####
+    .####
+    .####
+    .#.#.
+    .#...
+    .....
+
+    #####
+    ##.##
+    .#.##
+    ...##
+    ...#.
+    ...#.
+    .....
+
+    .....
+    #....
+    #....
+    #...#
+    #.#.#
+    #.###
+    #####
+
+    .....
+    .....
+    #.#..
+    ###..
+    ###.#
+    ###.#
+    #####
+
+    .....
+    .....
+    .....
+    #....
+    #.#..
+    #.#.#
+    #####"#;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 3);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 0);
+    }
+}
@@ -0,0 +1,474 @@
+//! Day 3's memory is an instruction language buried in noise. [`tokens`] splits it into words,
+//! numbers and punctuation with their byte spans, and an [`Interpreter`] runs every intact
+//! instruction from its table, so new instructions only need a table entry.
+
+use std::{
+    fmt::Display,
+    ops::{Range, RangeInclusive},
+};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use serde_json::json;
+
+use crate::{
+    explain::{Explain, Explanation},
+    generate::{Generate, Rng},
+    params::params,
+    parse::ParseError,
+    solution::{Solution, SolveError},
+};
+
+#[aoc_generator(day3)]
+fn parse(input: &str) -> String {
+    input.trim().to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Letters, apostrophes and underscores, so `don't` is one word.
+    Word,
+    Number,
+    Open,
+    Close,
+    Comma,
+    /// A run of anything else, whitespace included.
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// Where the token sits in the memory, in bytes.
+    pub span: Range<usize>,
+}
+
+fn kind(c: char) -> TokenKind {
+    match c {
+        '(' => TokenKind::Open,
+        ')' => TokenKind::Close,
+        ',' => TokenKind::Comma,
+        '0'..='9' => TokenKind::Number,
+        'a'..='z' | 'A'..='Z' | '\'' | '_' => TokenKind::Word,
+        _ => TokenKind::Other,
+    }
+}
+
+/// Splits `memory` into tokens. Words, numbers and runs of other characters are as long as
+/// they go, punctuation is a token per character.
+pub fn tokens(memory: &str) -> impl Iterator<Item = Token> + '_ {
+    let mut chars = memory.char_indices().peekable();
+    std::iter::from_fn(move || {
+        let (start, c) = chars.next()?;
+        let kind = kind(c);
+        let mut end = start + c.len_utf8();
+        if matches!(kind, TokenKind::Word | TokenKind::Number | TokenKind::Other) {
+            while let Some((i, c)) = chars.next_if(|&(_, c)| self::kind(c) == kind) {
+                end = i + c.len_utf8();
+            }
+        }
+        Some(Token {
+            kind,
+            span: start..end,
+        })
+    })
+}
+
+/// What instructions act on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Machine {
+    /// Whether instructions that aren't [`Instruction::always`] run.
+    pub enabled: bool,
+    pub total: i64,
+}
+
+impl Default for Machine {
+    fn default() -> Self {
+        Machine {
+            enabled: true,
+            total: 0,
+        }
+    }
+}
+
+/// An entry in an [`Interpreter`]'s table: `name` followed by exactly `arity` numbers between
+/// brackets, separated by commas.
+#[derive(Debug, Clone, Copy)]
+pub struct Instruction {
+    pub name: &'static str,
+    pub arity: usize,
+    /// Whether it runs while the machine is disabled, as `do()` has to.
+    pub always: bool,
+    pub run: fn(&mut Machine, &[i64]) -> Result<(), SolveError>,
+}
+
+pub const MUL: Instruction = Instruction {
+    name: "mul",
+    arity: 2,
+    always: false,
+    run: |machine, args| {
+        machine.total = (args[0].checked_mul(args[1]))
+            .and_then(|product| machine.total.checked_add(product))
+            .ok_or_else(|| SolveError::new("the total doesn't fit in 64 bits"))?;
+        Ok(())
+    },
+};
+
+pub const DO: Instruction = Instruction {
+    name: "do",
+    arity: 0,
+    always: true,
+    run: |machine, _| {
+        machine.enabled = true;
+        Ok(())
+    },
+};
+
+pub const DONT: Instruction = Instruction {
+    name: "don't",
+    arity: 0,
+    always: true,
+    run: |machine, _| {
+        machine.enabled = false;
+        Ok(())
+    },
+};
+
+/// The instructions each part knows about.
+pub const TABLES: [&[Instruction]; 2] = [&[MUL], &[MUL, DO, DONT]];
+
+/// An instruction found in the memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub name: &'static str,
+    pub args: Vec<i64>,
+    /// From the start of the name to the closing bracket, in bytes.
+    pub span: Range<usize>,
+    /// Whether it ran, rather than being skipped while the machine was disabled.
+    pub executed: bool,
+}
+
+/// Runs the instructions of a table that are intact in a memory and ignores everything else.
+#[derive(Debug, Clone)]
+pub struct Interpreter<'t> {
+    table: &'t [Instruction],
+    /// How many digits an operand may have.
+    digits: RangeInclusive<usize>,
+}
+
+impl<'t> Interpreter<'t> {
+    /// An interpreter for `table` that takes operands of one to three digits.
+    pub fn new(table: &'t [Instruction]) -> Self {
+        Interpreter {
+            table,
+            digits: 1..=3,
+        }
+    }
+
+    pub fn digits(self, digits: RangeInclusive<usize>) -> Self {
+        Interpreter { digits, ..self }
+    }
+
+    /// The instruction whose name ends `word`, preferring the longest name, so the `mul` in
+    /// `xmul` counts and `undo` is a `do`.
+    fn lookup(&self, word: &str) -> Option<&'t Instruction> {
+        self.table
+            .iter()
+            .filter(|instruction| word.ends_with(instruction.name))
+            .max_by_key(|instruction| instruction.name.len())
+    }
+
+    /// The operands of `instruction` if the tokens after its name are exactly the brackets and
+    /// numbers it takes, together with the index of the closing bracket.
+    fn operands(
+        &self,
+        memory: &str,
+        tokens: &[Token],
+        instruction: &Instruction,
+    ) -> Option<(Vec<i64>, usize)> {
+        let mut at = 0;
+        let mut expect = |kind: TokenKind| {
+            let token = tokens.get(at).filter(|token| token.kind == kind)?;
+            at += 1;
+            Some(&memory[token.span.clone()])
+        };
+        expect(TokenKind::Open)?;
+        let mut args = Vec::with_capacity(instruction.arity);
+        for i in 0..instruction.arity {
+            if i > 0 {
+                expect(TokenKind::Comma)?;
+            }
+            let digits = expect(TokenKind::Number)?;
+            if !self.digits.contains(&digits.len()) {
+                return None;
+            }
+            args.push(digits.parse().ok()?);
+        }
+        expect(TokenKind::Close)?;
+        Some((args, at - 1))
+    }
+
+    /// Runs every intact instruction in `memory`, handing each to `trace` whether it ran or
+    /// was skipped, and returns the total.
+    pub fn run(&self, memory: &str, mut trace: impl FnMut(Step)) -> Result<i64, SolveError> {
+        let tokens = tokens(memory).collect::<Vec<_>>();
+        let mut machine = Machine::default();
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = &tokens[i];
+            i += 1;
+            if token.kind != TokenKind::Word {
+                continue;
+            }
+            let word = &memory[token.span.clone()];
+            let Some(instruction) = self.lookup(word) else {
+                continue;
+            };
+            let Some((args, close)) = self.operands(memory, &tokens[i..], instruction) else {
+                continue;
+            };
+            let executed = instruction.always || machine.enabled;
+            if executed {
+                (instruction.run)(&mut machine, &args)?;
+            }
+            trace(Step {
+                name: instruction.name,
+                args,
+                span: token.span.end - instruction.name.len()..tokens[i + close].span.end,
+                executed,
+            });
+            i += close + 1;
+        }
+        Ok(machine.total)
+    }
+
+    pub fn total(&self, memory: &str) -> Result<i64, SolveError> {
+        self.run(memory, |_| {})
+    }
+
+    /// The total and every instruction found on the way, in order.
+    pub fn trace(&self, memory: &str) -> Result<(i64, Vec<Step>), SolveError> {
+        let mut steps = Vec::new();
+        let total = self.run(memory, |step| steps.push(step))?;
+        Ok((total, steps))
+    }
+}
+
+params! {
+    pub struct Params {
+        /// The most digits an operand may have.
+        max_digits: usize = 3,
+    }
+}
+
+fn interpreter(params: &Params, part: u8) -> Interpreter<'static> {
+    Interpreter::new(TABLES[part as usize - 1]).digits(1..=params.max_digits)
+}
+
+#[aoc(day3, part1)]
+fn part1(input: &str) -> Result<i64, SolveError> {
+    interpreter(&Params::default(), 1).total(input)
+}
+
+#[aoc(day3, part2)]
+fn part2(input: &str) -> Result<i64, SolveError> {
+    interpreter(&Params::default(), 2).total(input)
+}
+
+/// Day 3: Mull It Over.
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = String;
+    type Params = Params;
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 3;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse(input))
+    }
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        interpreter(params, 1).total(input)
+    }
+
+    fn part2(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        interpreter(params, 2).total(input)
+    }
+}
+
+/// Traces every instruction found, with its byte span and whether it ran.
+impl Explain for Day3 {
+    fn explain(input: &String, params: &Params, part: u8) -> Explanation {
+        let mut steps = Vec::new();
+        let mut skipped = 0;
+        let total = interpreter(params, part).run(input, |step| {
+            skipped += usize::from(!step.executed);
+            let args = step.args.iter().map(i64::to_string).collect::<Vec<_>>();
+            steps.push(json!({
+                "at": [step.span.start, step.span.end],
+                "instruction": format!("{}({})", step.name, args.join(",")),
+                "executed": step.executed,
+            }));
+        });
+        let witness = json!({ "found": steps.len(), "skipped": skipped, "steps": steps });
+        match total {
+            Ok(total) => Explanation::new(total, witness),
+            Err(_) => Explanation::new("none", witness),
+        }
+    }
+}
+
+params! {
+    /// How much memory to generate and what to fill it with.
+    pub struct Knobs {
+        /// Roughly how many characters of memory to make.
+        length: usize = 18000,
+        /// Chance that the next piece of memory is an intact `mul`.
+        muls: f64 = 0.2,
+        /// Chance that the next piece is a `do()` or `don't()`.
+        conditionals: f64 = 0.03,
+    }
+}
+
+/// Corrupted memory: intact instructions between near misses and line noise.
+impl Generate for Day3 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        const NEAR_MISSES: [&str; 11] = [
+            "mul(4*",
+            "mul[3,7]",
+            "mul ( 2 , 4 )",
+            "mul(32,64]",
+            "?(12,34)",
+            "don't",
+            "do(",
+            "from()",
+            "select()",
+            "mul(6,9!",
+            "mul(1234,5)",
+        ];
+        const NOISE: &[u8] = b"!@#$%^&*()[]{}<>?,'+-_/ :;whatwhyhowwherewhen";
+        let mut memory = String::new();
+        while memory.len() < knobs.length {
+            if rng.chance(knobs.muls) {
+                let (a, b) = (rng.between(1, 999), rng.between(1, 999));
+                memory.push_str(&format!("mul({a},{b})"));
+            } else if rng.chance(knobs.conditionals) {
+                memory.push_str(if rng.chance(0.5) { "do()" } else { "don't()" });
+            } else if rng.chance(0.2) {
+                memory.push_str(rng.pick::<&str>(&NEAR_MISSES));
+            } else {
+                memory.push(*rng.pick(NOISE) as char);
+            }
+        }
+        memory
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str =
+        r#"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"#;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            part1(&parse(
+                r#"xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))"#
+            ))
+            .unwrap(),
+            161
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE)).unwrap(), 48);
+    }
+
+    #[test]
+    fn numbers_have_at_most_three_digits() {
+        let memory = "mul(1234,5)mul(2,3)mul(99999999999,2)mul(,4)";
+        assert_eq!(part1(memory).unwrap(), 6);
+        let longer = Params { max_digits: 4 };
+        assert_eq!(
+            Day3::part1(&memory.into(), &longer).unwrap().to_string(),
+            "6176"
+        );
+        let huge = "mul(999999999999,999999999999)";
+        let error = interpreter(&Params { max_digits: 12 }, 1).total(huge);
+        assert!(error.is_err());
+    }
+
+    #[test]
+    fn tokens_have_byte_spans() {
+        let memory = "xmul(2,40)é don't";
+        let spans = tokens(memory)
+            .map(|token| (token.kind, &memory[token.span]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            spans,
+            [
+                (TokenKind::Word, "xmul"),
+                (TokenKind::Open, "("),
+                (TokenKind::Number, "2"),
+                (TokenKind::Comma, ","),
+                (TokenKind::Number, "40"),
+                (TokenKind::Close, ")"),
+                (TokenKind::Other, "é "),
+                (TokenKind::Word, "don't"),
+            ]
+        );
+    }
+
+    #[test]
+    fn traces_executed_and_skipped_instructions() {
+        let (total, steps) = interpreter(&Params::default(), 2).trace(EXAMPLE).unwrap();
+        assert_eq!(total, 48);
+        let trace = steps
+            .iter()
+            .map(|step| (&EXAMPLE[step.span.clone()], step.executed))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            trace,
+            [
+                ("mul(2,4)", true),
+                ("don't()", true),
+                ("mul(5,5)", false),
+                ("mul(11,8)", false),
+                ("do()", true),
+                ("mul(8,5)", true),
+            ]
+        );
+        assert_eq!(steps[2].args, [5, 5]);
+    }
+
+    #[test]
+    fn new_instructions_only_need_a_table_entry() {
+        const ADD: Instruction = Instruction {
+            name: "add",
+            arity: 3,
+            always: false,
+            run: |machine, args| {
+                machine.total += args.iter().sum::<i64>();
+                Ok(())
+            },
+        };
+        let table = [MUL, ADD, DONT];
+        let memory = "add(1,2,3)mul(2,2)add(1,2)don't()add(4,5,6)";
+        assert_eq!(Interpreter::new(&table).total(memory).unwrap(), 10);
+        let explanation = Day3::explain(&EXAMPLE.to_string(), &Params::default(), 2);
+        assert_eq!(explanation.answer, "48");
+        assert_eq!(
+            (
+                explanation.witness["found"].as_u64(),
+                explanation.witness["skipped"].as_u64()
+            ),
+            (Some(6), Some(2))
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt::Display;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+use serde_json::json;
+
+use crate::{
+    explain::{Explain, Explanation},
+    generate::{Generate, Rng},
+    grid::{Grid, ALL_DIRECTIONS},
+    params::params,
+    parse::ParseError,
+    pattern::{self, find_stencil, Stencil, StencilMatch, Symmetry, WordMatch},
+    solution::{Solution, SolveError},
+};
+
+type Input = Grid<char>;
+
+#[aoc_generator(day4)]
+fn parse(input: &str) -> Result<Input, ParseError> {
+    Grid::parse_chars(input)
+}
+
+params! {
+    pub struct Params {
+        /// The words to look for in part 1, separated by commas.
+        words: String = "XMAS".to_string(),
+    }
+}
+
+/// The `X` of two `MAS` crossing on their `A`, either way round.
+const X_MAS: &str = "M.S\n.A.\nM.S";
+
+fn words(params: &Params) -> Vec<Vec<char>> {
+    (params.words.split(','))
+        .map(|word| word.trim().chars().collect())
+        .filter(|word: &Vec<char>| !word.is_empty())
+        .collect()
+}
+
+fn find_words(grid: &Input, params: &Params) -> Vec<WordMatch> {
+    let words = words(params);
+    pattern::find_words(
+        grid,
+        &words.iter().map(Vec::as_slice).collect_vec(),
+        &ALL_DIRECTIONS,
+    )
+}
+
+fn find_crosses(grid: &Input) -> Vec<StencilMatch> {
+    let stencil = Stencil::parse(X_MAS, '.').expect("the X-MAS stencil parses");
+    find_stencil(grid, &stencil, Symmetry::Rotations)
+}
+
+#[aoc(day4, part1)]
+fn part1(grid: &Input) -> usize {
+    find_words(grid, &Params::default()).len()
+}
+
+#[aoc(day4, part2)]
+fn part2(grid: &Input) -> usize {
+    find_crosses(grid).len()
+}
+
+/// Day 4: Ceres Search.
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Input;
+    type Params = Params;
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 4;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(find_words(input, params).len())
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part2(input))
+    }
+}
+
+/// Lists where each match starts and which way it lies, above the grid with everything but
+/// the matches blanked out.
+impl Explain for Day4 {
+    fn explain(grid: &Input, params: &Params, part: u8) -> Explanation {
+        let (matches, cells): (Vec<_>, Vec<_>) = if part == 1 {
+            let words = words(params);
+            find_words(grid, params)
+                .into_iter()
+                .map(|m| {
+                    let word = words[m.word].iter().collect::<String>();
+                    let found = json!({
+                        "word": word,
+                        "at": [m.start.x, m.start.y],
+                        "heading": pattern::heading(m.direction),
+                    });
+                    (found, m.cells().collect_vec())
+                })
+                .unzip()
+        } else {
+            find_crosses(grid)
+                .into_iter()
+                .map(|m| {
+                    let found = json!({
+                        "at": [m.corner.x, m.corner.y],
+                        "orientation": m.orientation.to_string(),
+                    });
+                    (found, m.cells)
+                })
+                .unzip()
+        };
+        let highlighted = pattern::highlight(grid, cells.into_iter().flatten());
+        Explanation::new(
+            matches.len(),
+            json!({ "matches": matches, "grid": highlighted.lines().collect_vec() }),
+        )
+    }
+}
+
+params! {
+    /// Width and height of the generated word search.
+    pub struct Knobs {
+        size: usize = 140,
+    }
+}
+
+/// A square of random `X`, `M`, `A` and `S`.
+impl Generate for Day4 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        (0..knobs.size)
+            .map(|_| {
+                (0..knobs.size)
+                    .map(|_| *rng.pick(&['X', 'M', 'A', 'S']))
+                    .join("")
+            })
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            part1(
+                &parse(
+                    r#"MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX"#
+                )
+                .unwrap()
+            ),
+            18
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            part2(
+                &parse(
+                    r#"MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX"#
+                )
+                .unwrap()
+            ),
+            9
+        );
+    }
+
+    #[test]
+    fn crosses_need_four_diagonals() {
+        assert_eq!(part2(&parse("MAS").unwrap()), 0);
+    }
+
+    #[test]
+    fn looks_for_any_words() {
+        let grid = parse("SAMX\nAXMA\nMAXS").unwrap();
+        let params = Params {
+            words: "XMAS, AX,".to_string(),
+        };
+        assert_eq!(Day4::part1(&grid, &params).unwrap().to_string(), "7");
+        let explanation = Day4::explain(&grid, &Params::default(), 1);
+        assert_eq!(explanation.answer, "1");
+        assert_eq!(explanation.witness["matches"][0]["heading"], "west");
+        assert_eq!(explanation.witness["grid"], json!(["SAMX", "....", "...."]));
+        let crosses = Day4::explain(&parse("S.M\n.A.\nS.M").unwrap(), &params, 2);
+        assert_eq!(crosses.witness["matches"][0]["orientation"], "turned 180°");
+    }
+}
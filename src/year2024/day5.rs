@@ -0,0 +1,349 @@
+use std::fmt::Display;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+use serde_json::json;
+
+use crate::{
+    explain::{Explain, Explanation},
+    generate::{Generate, Rng},
+    params::params,
+    parse::{self, number, numbers, ParseError},
+    precedence::Precedence,
+    solution::{Solution, SolveError, Variant},
+};
+
+type Input = (Vec<Rule>, Vec<Pages>);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rule((u32, u32));
+
+impl Rule {
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (before, after) = parse::split_once(input, line, "|")?;
+        Ok(Rule((number(input, before)?, number(input, after)?)))
+    }
+}
+
+fn precedence(rules: &[Rule]) -> Precedence<u32> {
+    rules.iter().map(|rule| rule.0).collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct Pages(Vec<u32>);
+
+impl Pages {
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let pages = numbers(input, line, ',')?;
+        if pages.is_empty() {
+            return Err(ParseError::at(input, line, "expected at least one page"));
+        }
+        if let Some(page) = pages.iter().duplicates().next() {
+            return Err(ParseError::at(
+                input,
+                line,
+                format!("page {page} appears twice"),
+            ));
+        }
+        Ok(Pages(pages))
+    }
+
+    fn is_valid(&self, rules: &[Rule]) -> bool {
+        self.0.iter().enumerate().all(|(ind, page)| {
+            rules.iter().all(|rule| {
+                rule.0 .0 != *page
+                    || (self.0[(ind + 1)..].contains(&rule.0 .1)
+                        || !self.0[0..ind].contains(&rule.0 .1))
+            })
+        })
+    }
+
+    fn is_valid_hash(&self, rules: &Precedence<u32>) -> bool {
+        rules.is_ordered(&self.0)
+    }
+
+    /// The middle page, widened so that adding up a middle page per update can't overflow.
+    fn middle(&self) -> u64 {
+        self.0[self.0.len() / 2].into()
+    }
+}
+
+#[aoc_generator(day5)]
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let [rules, updates] = parse::exact_sections(input)?;
+    let rules = parse::lines(rules)
+        .map(|l| Rule::parse(input, l))
+        .collect::<Result<_, _>>()?;
+    let updates = parse::lines(updates)
+        .map(|l| Pages::parse(input, l))
+        .collect::<Result<_, _>>()?;
+    Ok((rules, updates))
+}
+
+#[aoc(day5, part1)]
+fn part1((rules, pages): &Input) -> u64 {
+    pages
+        .iter()
+        .filter_map(|pages| {
+            if pages.is_valid(rules) {
+                Some(pages.middle())
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+#[aoc(day5, part1, HASH)]
+fn solver_part1_hash((rules, pages): &Input) -> u64 {
+    let rules = precedence(rules);
+    pages
+        .iter()
+        .filter_map(|pages| {
+            if pages.is_valid_hash(&rules) {
+                Some(pages.middle())
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+/// The update in an order that keeps every rule between its pages.
+fn reorder(rules: &Precedence<u32>, pages: &Pages) -> Result<Vec<u32>, SolveError> {
+    rules.sort(&pages.0).map_err(|cycle| {
+        SolveError::new(format!(
+            "the rules for {} go round in a circle: {cycle}",
+            pages.0.iter().join(",")
+        ))
+    })
+}
+
+#[aoc(day5, part2)]
+fn part2((rules, pages): &Input) -> Result<u64, SolveError> {
+    let rules = precedence(rules);
+    pages
+        .iter()
+        .filter(|pages| !pages.is_valid_hash(&rules))
+        .map(|pages| Ok(Pages(reorder(&rules, pages)?).middle()))
+        .sum()
+}
+
+/// Day 5: Print Queue.
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Input;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 5;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        part2(input)
+    }
+
+    fn variants() -> Vec<Variant<Self>> {
+        vec![Variant {
+            part: 1,
+            name: "HASH",
+            solve: |input, _| Ok(solver_part1_hash(input).to_string()),
+        }]
+    }
+}
+
+/// Part 1 lists the updates already in order. Part 2 lists, for every other update, the rules
+/// it breaks, how few pages would have to move to fix it and the order it ends up in.
+impl Explain for Day5 {
+    fn explain((rules, updates): &Input, _: &(), part: u8) -> Explanation {
+        let rules = precedence(rules);
+        let (ordered, unordered): (Vec<_>, Vec<_>) =
+            (updates.iter().enumerate()).partition(|(_, pages)| pages.is_valid_hash(&rules));
+        if part == 1 {
+            return Explanation::new(
+                ordered.iter().map(|(_, pages)| pages.middle()).sum::<u64>(),
+                json!({ "ordered": ordered.iter().map(|&(i, _)| i).collect_vec() }),
+            );
+        }
+        let mut total = Some(0);
+        let fixes = unordered
+            .iter()
+            .map(|&(i, pages)| {
+                let breaks = (rules.violations(&pages.0).iter())
+                    .map(|(before, after)| format!("{before}|{after}"))
+                    .collect_vec();
+                let fixed = match reorder(&rules, pages) {
+                    Ok(fixed) => fixed,
+                    Err(e) => {
+                        total = None;
+                        return json!({ "update": i, "breaks": breaks, "error": e.message });
+                    }
+                };
+                total = total.map(|total| total + u64::from(fixed[fixed.len() / 2]));
+                // Sorting worked, so the rules between these pages have no cycle to report.
+                let moves = rules.moves_to_fix(&pages.0).ok();
+                json!({ "update": i, "breaks": breaks, "moves": moves, "fixed": fixed })
+            })
+            .collect_vec();
+        Explanation::new(
+            total.map_or("none".to_string(), |total| total.to_string()),
+            json!({ "fixes": fixes }),
+        )
+    }
+}
+
+params! {
+    /// How many pages and updates to generate, and how many updates start out in order.
+    pub struct Knobs {
+        /// Distinct page numbers, from 2 to 90 so there's a rule to list.
+        pages: usize = 49,
+        /// Updates to print, at least one.
+        updates: usize = 200,
+        /// Chance that an update is already in the right order.
+        ordered: f64 = 0.5,
+    }
+}
+
+/// Pages in a hidden order with a rule for every pair, so every update has exactly one right
+/// order. Updates have an odd number of pages so they have a middle one.
+impl Generate for Day5 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let mut order: Vec<u32> = (10..100).collect();
+        rng.shuffle(&mut order);
+        order.truncate(knobs.pages.clamp(2, 90));
+        let mut rules: Vec<_> = (0..order.len())
+            .flat_map(|i| (i + 1..order.len()).map(move |j| (i, j)))
+            .map(|(i, j)| format!("{}|{}", order[i], order[j]))
+            .collect();
+        rng.shuffle(&mut rules);
+        let longest = (order.len().min(23) - 1) | 1;
+        let mut updates = (0..knobs.updates.max(1)).map(|_| {
+            let length = (rng.between(5.min(longest) as isize, longest as isize) as usize) | 1;
+            let mut ranks: Vec<_> = (0..order.len()).collect();
+            rng.shuffle(&mut ranks);
+            ranks.truncate(length);
+            if rng.chance(knobs.ordered) {
+                ranks.sort();
+            }
+            ranks.iter().map(|&i| order[i]).join(",")
+        });
+        format!("{}\n\n{}", rules.join("\n"), updates.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXAMPLE: &str = r#"47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47"#;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 143);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), Ok(123));
+    }
+
+    #[test]
+    fn adds_up_the_largest_pages() {
+        let input =
+            parse("1|4294967295\n\n1,4294967295,2\n3,4294967295,4\n2,4294967295,1").unwrap();
+        assert_eq!(part1(&input), 2 * 4294967295);
+        assert_eq!(solver_part1_hash(&input), 2 * 4294967295);
+        assert_eq!(part2(&input), Ok(1));
+        let twice = parse("4294967295|1\n\n1,2,4294967295\n1,3,4294967295").unwrap();
+        assert_eq!(part2(&twice), Ok(4294967295 * 2));
+        assert_eq!(Day5::explain(&twice, &(), 2).answer, "8589934590");
+    }
+
+    #[test]
+    fn explains_what_each_update_gets_wrong() {
+        let explanation = Day5::explain(&parse(EXAMPLE).unwrap(), &(), 2);
+        assert_eq!(explanation.answer, "123");
+        let fixes = &explanation.witness["fixes"];
+        assert_eq!(fixes[0]["update"], 3);
+        assert_eq!(fixes[0]["breaks"], json!(["97|75"]));
+        assert_eq!(fixes[0]["fixed"], json!([97, 75, 47, 61, 53]));
+        assert_eq!(fixes[2]["breaks"].as_array().unwrap().len(), 4);
+        assert_eq!(fixes[2]["moves"], 2);
+        let ordered = Day5::explain(&parse(EXAMPLE).unwrap(), &(), 1);
+        assert_eq!(ordered.witness["ordered"], json!([0, 1, 2]));
+    }
+
+    #[test]
+    fn reports_cycles_between_the_pages_of_an_update() {
+        let input = parse("1|2\n2|3\n3|1\n\n1,2\n2,1,3").unwrap();
+        let error = part2(&input).unwrap_err();
+        assert_eq!(
+            error.message,
+            "the rules for 2,1,3 go round in a circle: 2 before 3 before 1 before 2"
+        );
+        assert_eq!(Day5::explain(&input, &(), 2).answer, "none");
+        let error = parse("1|2\n\n1,2,1").unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (3, "page 1 appears twice")
+        );
+    }
+
+    fn rules_and_pages() -> impl Strategy<Value = (Vec<Rule>, Pages)> {
+        (
+            prop::collection::vec((1u32..10, 1u32..10), 0..30),
+            prop::collection::vec(1u32..10, 1..8),
+        )
+            .prop_map(|(rules, pages)| {
+                let pages = Pages(pages.into_iter().unique().collect());
+                (rules.into_iter().map(Rule).collect(), pages)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn hash_lookup_agrees_with_scanning_the_rules((rules, pages) in rules_and_pages()) {
+            let hashed = precedence(&rules);
+            prop_assert_eq!(pages.is_valid(&rules), pages.is_valid_hash(&hashed));
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use std::{fmt::Display, io};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    generate::{Generate, Rng},
+    grid::{Grid, CARDINALS},
+    params::params,
+    parse::ParseError,
+    point::Point,
+    solution::{Solution, SolveError, Variant},
+    visualize::{Animate, Cell, Frame, Sink, ACTOR, FLOOR, TRAIL, WALL},
+};
+
+#[aoc_generator(day6)]
+fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    let grid = Grid::parse_only(input, b".#^")?;
+    grid.require(input, b'^')?;
+    Ok(grid)
+}
+
+fn start(grid: &Grid<u8>) -> Point {
+    grid.find(&b'^').unwrap()
+}
+
+/// The guard's next position and direction: a step forward, or a quarter turn to the right if
+/// the way is blocked. `None` once it would walk off the map.
+fn step(m: &Grid<u8>, position: Point, direction: usize) -> Option<(Point, usize)> {
+    let next = position + CARDINALS[direction];
+    match m.get(&next)? {
+        b'#' => Some((position, (direction + 1) % 4)),
+        _ => Some((next, direction)),
+    }
+}
+
+fn walk(m: &Grid<u8>, mut position: Point, return_squares: bool) -> Option<Vec<Point>> {
+    let mut seen = m.same_size_with([false; 4]);
+    let mut direction = 0;
+    loop {
+        if seen[position][direction] {
+            return None;
+        }
+        seen[position][direction] = true;
+        match step(m, position, direction) {
+            None => {
+                if !return_squares {
+                    return Some(Vec::new());
+                }
+                let visited = seen
+                    .iter()
+                    .filter(|(_, dirs)| dirs.iter().any(|&b| b))
+                    .map(|(p, _)| p)
+                    .collect();
+                return Some(visited);
+            }
+            Some(next) => (position, direction) = next,
+        }
+    }
+}
+
+/// Every square the guard visits on the way off the map.
+fn route(grid: &Grid<u8>) -> Result<Vec<Point>, SolveError> {
+    walk(grid, start(grid), true).ok_or_else(|| SolveError::new("the guard never leaves the map"))
+}
+
+#[aoc(day6, part1)]
+fn part1(grid: &Grid<u8>) -> Result<usize, SolveError> {
+    Ok(route(grid)?.len())
+}
+
+#[aoc(day6, part2)]
+fn part2(grid: &Grid<u8>) -> Result<usize, SolveError> {
+    let start = start(grid);
+    let mut obstacles = grid.clone();
+    Ok(route(grid)?
+        .iter()
+        .filter(|&&point| {
+            let previous = obstacles[point];
+            obstacles[point] = b'#';
+            let ok = walk(&obstacles, start, false).is_none();
+            obstacles[point] = previous;
+            ok
+        })
+        .count())
+}
+
+#[cfg(feature = "parallel")]
+#[aoc(day6, part2, PARALLEL)]
+fn part2_parallel(grid: &Grid<u8>) -> Result<usize, SolveError> {
+    let start = start(grid);
+    Ok(route(grid)?
+        .par_iter()
+        .map_init(
+            || grid.clone(),
+            |obstacles, &point| {
+                let previous = obstacles[point];
+                obstacles[point] = b'#';
+                let ok = walk(obstacles, start, false).is_none();
+                obstacles[point] = previous;
+                ok
+            },
+        )
+        .filter(|&ok| ok)
+        .count())
+}
+
+/// Day 6: Guard Gallivant.
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Grid<u8>;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 6;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        part2(input)
+    }
+
+    fn variants() -> Vec<Variant<Self>> {
+        vec![
+            #[cfg(feature = "parallel")]
+            Variant {
+                part: 2,
+                name: "PARALLEL",
+                solve: |input, _| Ok(part2_parallel(input)?.to_string()),
+            },
+        ]
+    }
+}
+
+/// Follows the guard until it leaves the map, leaving a trail behind it.
+impl Animate for Day6 {
+    fn animate(grid: &Grid<u8>, _: &(), sink: &mut dyn Sink) -> io::Result<()> {
+        let mut frame = Frame::from_grid(grid, |_, &b| match b {
+            b'#' => Cell::new('#', WALL),
+            _ => Cell::new('.', FLOOR),
+        });
+        let mut seen = grid.same_size_with([false; 4]);
+        let mut guard = Some((start(grid), 0));
+        let mut visited = 0;
+        while let Some((position, direction)) = guard {
+            if seen[position][direction] {
+                frame.caption = format!("{visited} squares visited, stuck in a loop");
+                return sink.frame(&frame);
+            }
+            if !seen[position].contains(&true) {
+                visited += 1;
+            }
+            seen[position][direction] = true;
+            frame.draw(position, ['^', '>', 'v', '<'][direction], ACTOR);
+            frame.caption = format!("{visited} squares visited");
+            sink.frame(&frame)?;
+            frame.draw(position, 'X', TRAIL);
+            guard = step(grid, position, direction);
+        }
+        Ok(())
+    }
+}
+
+params! {
+    /// Size of the generated lab and how cluttered it is.
+    pub struct Knobs {
+        size: usize = 130,
+        /// Chance that a square holds an obstruction.
+        obstructions: f64 = 0.05,
+    }
+}
+
+/// A lab with scattered obstructions and the guard on a random free square. Labs where the
+/// guard never leaves are thrown away, as the puzzle promises it does.
+impl Generate for Day6 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        let size = knobs.size.max(1);
+        loop {
+            let mut lab = Grid::new(size, size, b'.');
+            for point in lab.points().collect_vec() {
+                if rng.chance(knobs.obstructions) {
+                    lab[point] = b'#';
+                }
+            }
+            let guard = lab.point_of(rng.below(lab.len()));
+            lab[guard] = b'^';
+            if walk(&lab, guard, false).is_some() {
+                return lab.to_string();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#..."#;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()).unwrap(), 41);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()).unwrap(), 6);
+    }
+
+    #[test]
+    fn reports_guards_that_never_leave() {
+        let grid = parse(".#..\n...#\n#^..\n..#.").unwrap();
+        assert_eq!(
+            part1(&grid).unwrap_err().message,
+            "the guard never leaves the map"
+        );
+        assert!(part2(&grid).is_err());
+    }
+
+    #[test]
+    fn animates_the_walk() {
+        let mut frames = Vec::new();
+        Day6::animate(&parse(EXAMPLE).unwrap(), &(), &mut frames).unwrap();
+        let last = frames.last().unwrap();
+        assert_eq!(last.caption, "41 squares visited");
+        assert_eq!(last.cells[Point::new(7, 9)].glyph, 'v');
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_serial() {
+        assert_eq!(part2_parallel(&parse(EXAMPLE).unwrap()).unwrap(), 6);
+    }
+}
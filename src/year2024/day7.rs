@@ -0,0 +1,453 @@
+use std::fmt::Display;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde_json::json;
+
+use crate::{
+    explain::{Explain, Explanation},
+    generate::{Generate, Rng},
+    params::params,
+    parse::{self, number, numbers, ParseError},
+    solution::{Solution, SolveError, Variant},
+    stream::Stream,
+};
+
+type Input = Vec<(u64, Vec<u64>)>;
+
+#[aoc_generator(day7)]
+fn parse(input: &str) -> Result<Input, ParseError> {
+    parse::lines(input)
+        .map(|l| {
+            let (target, values) = parse::split_once(input, l, ":")?;
+            let values = numbers(input, values, ' ')?;
+            if values.is_empty() {
+                return Err(ParseError::at(input, l, "expected numbers after `:`"));
+            }
+            Ok((number(input, target.trim())?, values))
+        })
+        .collect()
+}
+
+/// Whether `so_far` is past `target` for good. Every operator only makes numbers larger, except
+/// multiplying by zero.
+fn overshot(target: u64, so_far: u64, rest: &[u64]) -> bool {
+    so_far > target && !rest.contains(&0)
+}
+
+/// Whether some operators between `values` make `target`.
+fn balances(hold: fn(u64, u64, &[u64]) -> bool, target: u64, values: &[u64]) -> bool {
+    values
+        .split_first()
+        .is_some_and(|(&first, rest)| hold(target, first, rest))
+}
+
+fn hold_true_part_1(target: u64, so_far: u64, rest: &[u64]) -> bool {
+    if overshot(target, so_far, rest) {
+        return false;
+    }
+    if rest.is_empty() {
+        return so_far == target;
+    }
+    let mul = so_far.checked_mul(rest[0]);
+    let add = so_far.checked_add(rest[0]);
+    // A value that doesn't fit in a u64 is past any target, just like one that is too big.
+    [add, mul]
+        .into_iter()
+        .flatten()
+        .any(|next| hold_true_part_1(target, next, &rest[1..]))
+}
+
+fn concat(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(10u64.checked_pow(b.checked_ilog10().unwrap_or(0) + 1)?)?
+        .checked_add(b)
+}
+
+fn hold_true_part_2(target: u64, so_far: u64, rest: &[u64]) -> bool {
+    if overshot(target, so_far, rest) {
+        return false;
+    }
+    if rest.is_empty() {
+        return so_far == target;
+    }
+    let mul = so_far.checked_mul(rest[0]);
+    let add = so_far.checked_add(rest[0]);
+    let concat_op = concat(so_far, rest[0]);
+    [add, mul, concat_op]
+        .into_iter()
+        .flatten()
+        .any(|next| hold_true_part_2(target, next, &rest[1..]))
+}
+
+fn hold_true_part_2_string(target: u64, so_far: u64, rest: &[u64]) -> bool {
+    if overshot(target, so_far, rest) {
+        return false;
+    }
+    if rest.is_empty() {
+        return so_far == target;
+    }
+    let mul = so_far.checked_mul(rest[0]);
+    let add = so_far.checked_add(rest[0]);
+    let concat_op = string_concat(so_far, rest[0]);
+    [add, mul, concat_op]
+        .into_iter()
+        .flatten()
+        .any(|next| hold_true_part_2_string(target, next, &rest[1..]))
+}
+
+/// The operators, applied left to right, that turn `so_far` and `rest` into `target`.
+fn find_operators(
+    target: u64,
+    so_far: u64,
+    rest: &[u64],
+    concatenate: bool,
+) -> Option<Vec<&'static str>> {
+    if overshot(target, so_far, rest) {
+        return None;
+    }
+    let Some((&next, rest)) = rest.split_first() else {
+        return (so_far == target).then(Vec::new);
+    };
+    let mut candidates = vec![
+        ("+", so_far.checked_add(next)),
+        ("*", so_far.checked_mul(next)),
+    ];
+    if concatenate {
+        candidates.push(("||", concat(so_far, next)));
+    }
+    candidates.into_iter().find_map(|(operator, value)| {
+        let mut operators = find_operators(target, value?, rest, concatenate)?;
+        operators.insert(0, operator);
+        Some(operators)
+    })
+}
+
+fn string_concat(a: u64, b: u64) -> Option<u64> {
+    format!("{}{}", a, b).parse().ok()
+}
+
+#[aoc(day7, part1)]
+fn part1(input: &Input) -> u128 {
+    input
+        .iter()
+        .filter(|(sum, values)| balances(hold_true_part_1, *sum, values))
+        .map(|&(sum, _)| sum as u128)
+        .sum()
+}
+
+#[aoc(day7, part2)]
+fn part2(input: &Input) -> u128 {
+    input
+        .iter()
+        .filter(|(sum, values)| balances(hold_true_part_2, *sum, values))
+        .map(|&(sum, _)| sum as u128)
+        .sum()
+}
+
+#[aoc(day7, part2, STRING_CONCAT)]
+fn part2_string_concat(input: &Input) -> u128 {
+    input
+        .iter()
+        .filter(|(sum, values)| balances(hold_true_part_2_string, *sum, values))
+        .map(|&(sum, _)| sum as u128)
+        .sum()
+}
+
+#[cfg(feature = "parallel")]
+#[aoc(day7, part1, PARALLEL)]
+fn part1_parallel(input: &Input) -> u128 {
+    input
+        .par_iter()
+        .filter(|(sum, values)| balances(hold_true_part_1, *sum, values))
+        .map(|&(sum, _)| sum as u128)
+        .sum()
+}
+
+#[cfg(feature = "parallel")]
+#[aoc(day7, part2, PARALLEL)]
+fn part2_parallel(input: &Input) -> u128 {
+    input
+        .par_iter()
+        .filter(|(sum, values)| balances(hold_true_part_2, *sum, values))
+        .map(|&(sum, _)| sum as u128)
+        .sum()
+}
+
+/// Day 7: Bridge Repair.
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Input;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 7;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part2(input))
+    }
+
+    fn variants() -> Vec<Variant<Self>> {
+        vec![
+            Variant {
+                part: 2,
+                name: "STRING_CONCAT",
+                solve: |input, _| Ok(part2_string_concat(input).to_string()),
+            },
+            #[cfg(feature = "parallel")]
+            Variant {
+                part: 1,
+                name: "PARALLEL",
+                solve: |input, _| Ok(part1_parallel(input).to_string()),
+            },
+            #[cfg(feature = "parallel")]
+            Variant {
+                part: 2,
+                name: "PARALLEL",
+                solve: |input, _| Ok(part2_parallel(input).to_string()),
+            },
+        ]
+    }
+}
+
+/// Sums the true equations as they go by, in 128 bits like the other solvers so that no number
+/// of `u64` targets can overflow them.
+impl Stream for Day7 {
+    type State = [u128; 2];
+
+    fn fold(totals: &mut [u128; 2], record: &str, _: &()) -> Result<(), ParseError> {
+        for (target, values) in parse(record)? {
+            if balances(hold_true_part_1, target, &values) {
+                totals[0] += target as u128;
+            }
+            if balances(hold_true_part_2, target, &values) {
+                totals[1] += target as u128;
+            }
+        }
+        Ok(())
+    }
+
+    fn answer(totals: &[u128; 2], _: &(), part: u8) -> Result<String, SolveError> {
+        Ok(totals[part as usize - 1].to_string())
+    }
+}
+
+/// Lists every equation that can be balanced, written out with the operators that do it.
+impl Explain for Day7 {
+    fn explain(input: &Input, _: &(), part: u8) -> Explanation {
+        let equations = input
+            .iter()
+            .filter_map(|(target, values)| {
+                let operators = find_operators(*target, values[0], &values[1..], part == 2)?;
+                let expression = values
+                    .iter()
+                    .map(u64::to_string)
+                    .interleave(operators.into_iter().map(str::to_string))
+                    .join(" ");
+                Some((*target, expression))
+            })
+            .collect_vec();
+        Explanation::new(
+            equations
+                .iter()
+                .map(|&(target, _)| target as u128)
+                .sum::<u128>(),
+            json!({
+                "balanced": equations.len(),
+                "of": input.len(),
+                "equations": equations
+                    .iter()
+                    .map(|(target, expression)| format!("{target} = {expression}"))
+                    .collect_vec(),
+            }),
+        )
+    }
+}
+
+params! {
+    /// How many calibration equations to generate, and how long they get.
+    pub struct Knobs {
+        equations: usize = 850,
+        max_values: usize = 12,
+        /// Chance that an equation is built from operators rather than made up.
+        balanced: f64 = 0.5,
+    }
+}
+
+/// Equations built by putting random operators between small numbers, and some with the
+/// result nudged so they likely don't balance. Results stay below 10^15.
+impl Generate for Day7 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        const LIMIT: u64 = 1_000_000_000_000_000;
+        let longest = knobs.max_values.max(2);
+        let mut equations = Vec::new();
+        while equations.len() < knobs.equations {
+            let count = rng.between(2, longest as isize) as usize;
+            let values = (0..count)
+                .map(|_| match rng.below(3) {
+                    0 => rng.between(1, 9),
+                    1 => rng.between(10, 99),
+                    _ => rng.between(100, 999),
+                } as u64)
+                .collect_vec();
+            let target = values[1..].iter().try_fold(values[0], |so_far, &next| {
+                match rng.below(3) {
+                    0 => so_far.checked_add(next),
+                    1 => so_far.checked_mul(next),
+                    _ => so_far
+                        .checked_mul(10u64.pow(next.ilog10() + 1))?
+                        .checked_add(next),
+                }
+                .filter(|&v| v < LIMIT)
+            });
+            let Some(mut target) = target else {
+                continue;
+            };
+            if !rng.chance(knobs.balanced) {
+                target += rng.between(1, 9) as u64;
+            }
+            equations.push(format!("{target}: {}", values.iter().join(" ")));
+        }
+        equations.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    pub const EXAMPLE: &str = r#"190: 10 19
+    3267: 81 40 27
+    83: 17 5
+    156: 15 6
+    7290: 6 8 6 15
+    161011: 16 10 13
+    192: 17 8 14
+    21037: 9 7 18 13
+    292: 11 6 16 20"#;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 3749);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_serial() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part1_parallel(&input), part1(&input));
+        assert_eq!(part2_parallel(&input), part2(&input));
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let error = parse("190: 10 19\n3267 81 40 27").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        let error = parse("190: 10 1x9").unwrap_err();
+        assert_eq!((error.line, error.column, error.width), (1, 9, 3));
+    }
+
+    #[test]
+    fn concatting_works() {
+        let a = 15u64;
+        let b = 6u64;
+        assert_eq!(concat(a, b), Some(156u64));
+        let a = 15123u64;
+        let b = 1231u64;
+        assert_eq!(concat(a, b), Some(151231231u64));
+        assert_eq!(concat(u64::MAX / 10, 99), None);
+        assert_eq!(string_concat(u64::MAX / 10, 99), None);
+        assert!(!hold_true_part_2(u64::MAX, u64::MAX, &[99]));
+    }
+
+    #[test]
+    fn multiplies_by_zero() {
+        let input = parse("7: 0 5 7\n0: 5 0\n5: 1 0 5").unwrap();
+        assert_eq!((part1(&input), part2(&input)), (12, 12));
+        assert_eq!(Day7::explain(&input, &(), 1).answer, "12");
+        assert_eq!(
+            Day7::explain(&input, &(), 2).witness["equations"][0],
+            "7 = 0 * 5 + 7"
+        );
+    }
+
+    #[test]
+    fn sums_in_128_bits() {
+        let input = parse(
+            "18446744073709551615: 18446744073709551615\n"
+                .repeat(2)
+                .as_str(),
+        )
+        .unwrap();
+        let total = 2 * u64::MAX as u128;
+        assert_eq!((part1(&input), part2(&input)), (total, total));
+        assert_eq!(part2_string_concat(&input), total);
+        assert_eq!(Day7::explain(&input, &(), 1).answer, total.to_string());
+    }
+
+    #[test]
+    fn test_ops() {
+        assert!(hold_true_part_2(156, 15, &[6]));
+        assert!(hold_true_part_2(7290, 6, &[8, 6, 15]));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 11387);
+    }
+
+    proptest! {
+        #[test]
+        fn concat_agrees_with_string_concat(a in 0u64..1_000_000, b in 0u64..1_000_000) {
+            prop_assert_eq!(concat(a, b), string_concat(a, b));
+        }
+
+        #[test]
+        fn both_concats_balance_the_same_equations(
+            target in 0u64..5000,
+            values in prop::collection::vec(0u64..30, 1..6),
+        ) {
+            prop_assert_eq!(
+                balances(hold_true_part_2, target, &values),
+                balances(hold_true_part_2_string, target, &values)
+            );
+        }
+
+        #[test]
+        fn explanations_balance_the_same_equations(
+            target in 0u64..200,
+            values in prop::collection::vec(0u64..8, 1..6),
+        ) {
+            let input = vec![(target, values)];
+            for part in [1, 2] {
+                let solved = if part == 1 { part1(&input) } else { part2(&input) };
+                prop_assert_eq!(Day7::explain(&input, &(), part).answer, solved.to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn explains_the_operators() {
+        let explanation = Day7::explain(&parse(EXAMPLE).unwrap(), &(), 2);
+        assert_eq!(explanation.answer, "11387");
+        assert_eq!(explanation.witness["balanced"], 6);
+        assert_eq!(
+            explanation.witness["equations"][3],
+            "7290 = 6 * 8 || 6 * 15"
+        );
+    }
+}
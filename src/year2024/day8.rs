@@ -1,31 +1,33 @@
+use std::fmt::Display;
+
 use aoc_runner_derive::{aoc, aoc_generator};
 
 use ahash::AHashMap as HashMap;
 use ahash::AHashSet as Set;
 use itertools::Itertools;
 
-use crate::point::Point;
+use crate::{
+    generate::{Generate, Rng},
+    grid::Grid,
+    params::params,
+    parse::ParseError,
+    point::Point,
+    solution::{Solution, SolveError},
+};
 
 pub type Frequency = char;
 pub type FrequencyMap = HashMap<Frequency, Vec<Point>>;
 pub type Input = (FrequencyMap, usize, usize);
 #[aoc_generator(day8)]
-fn parse(input: &str) -> Input {
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let grid = Grid::parse_chars(input)?;
     let mut map = HashMap::new();
-    input.lines().enumerate().for_each(|(y, l)| {
-        l.chars().enumerate().for_each(|(x, c)| {
-            if c != '.' {
-                let x = x as isize;
-                let y = y as isize;
-                map.entry(c).or_insert_with(Vec::new).push(Point { x, y });
-            }
-        });
-    });
-    (
-        map,
-        input.lines().count(),
-        input.lines().next().unwrap().len(),
-    )
+    for (point, &c) in grid.iter() {
+        if c != '.' {
+            map.entry(c).or_insert_with(Vec::new).push(point);
+        }
+    }
+    Ok((map, grid.height, grid.width))
 }
 
 #[aoc(day8, part1)]
@@ -34,8 +36,8 @@ fn part1(input: &Input) -> usize {
     let height = input.1;
     let all_antinodes: Set<Point> = input
         .0
-        .iter()
-        .flat_map(|(_, points)| {
+        .values()
+        .flat_map(|points| {
             let antinodes: Set<Point> = points
                 .iter()
                 .flat_map(|p| {
@@ -64,8 +66,8 @@ fn part2(input: &Input) -> usize {
     let height = input.1;
     let all_harmonics: Set<Point> = input
         .0
-        .iter()
-        .flat_map(|(_freq, points)| {
+        .values()
+        .flat_map(|points| {
             let antinodes: Set<Point> = points
                 .iter()
                 .flat_map(|p| {
@@ -93,6 +95,60 @@ fn part2(input: &Input) -> usize {
     all_harmonics.len()
 }
 
+/// Day 8: Resonant Collinearity.
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Input;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 8;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part2(input))
+    }
+}
+
+params! {
+    /// Size of the generated roof and the antennas on it.
+    pub struct Knobs {
+        size: usize = 50,
+        /// How many frequencies are in use, at most 62.
+        frequencies: usize = 40,
+        /// The most antennas sharing a frequency; every frequency has at least two.
+        antennas: usize = 4,
+    }
+}
+
+/// Antennas on distinct squares, tuned to letters and digits.
+impl Generate for Day8 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        const FREQUENCIES: &[u8] =
+            b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        let mut map = Grid::new(knobs.size.max(1), knobs.size.max(1), b'.');
+        let mut free = map.points().collect_vec();
+        rng.shuffle(&mut free);
+        for &frequency in FREQUENCIES.iter().take(knobs.frequencies) {
+            let count = rng.between(2, knobs.antennas.max(2) as isize) as usize;
+            for _ in 0..count.min(free.len()) {
+                map[free.pop().unwrap()] = frequency;
+            }
+        }
+        map.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,11 +167,11 @@ mod tests {
 ............"#;
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse(EXAMPLE)), 14);
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 14);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse(EXAMPLE)), 34);
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 34);
     }
 }
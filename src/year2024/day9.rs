@@ -0,0 +1,164 @@
+use std::fmt::Display;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::{
+    generate::{Generate, Rng},
+    params::params,
+    parse::ParseError,
+    solution::{Solution, SolveError},
+};
+
+#[aoc_generator(day9)]
+fn parse(input: &str) -> Result<Vec<u8>, ParseError> {
+    let disk_map = input.trim();
+    if disk_map.is_empty() {
+        return Err(ParseError::at_offset(input, 0, "expected a disk map"));
+    }
+    disk_map
+        .char_indices()
+        .map(|(i, c)| {
+            c.to_digit(10).map(|d| d as u8).ok_or_else(|| {
+                ParseError::at(input, &disk_map[i..i + c.len_utf8()], "expected a digit")
+            })
+        })
+        .collect()
+}
+
+fn solve(mut files: Vec<(usize, i32)>) -> usize {
+    let mut i = files.len().saturating_sub(1);
+    while i > 0 {
+        let (size, id) = files[i];
+        if id == -1 {
+            i -= 1;
+            continue;
+        }
+        if let Some(j) = files[0..i]
+            .iter()
+            .position(|&(s, id)| id == -1 && size <= s)
+        {
+            let s = files[j].0;
+            files[j] = (size, id);
+            files[i] = (size, -1);
+            if size < s {
+                files.insert(j + 1, (s - size, -1));
+            }
+        }
+        i -= 1;
+    }
+    files
+        .iter()
+        .flat_map(|&(s, id)| (0..s).map(move |_| id))
+        .enumerate()
+        .map(|(i, id)| if id == -1 { 0 } else { i * id as usize })
+        .sum()
+}
+
+#[aoc(day9, part1)]
+fn part1(input: &[u8]) -> usize {
+    let mut file_id = 0;
+    let mut fs1 = Vec::new();
+    for (i, &b) in input.iter().enumerate() {
+        let v = if i % 2 == 0 {
+            file_id += 1;
+            file_id - 1
+        } else {
+            -1
+        };
+        fs1.extend((0..b).map(|_| (1, v)));
+    }
+    solve(fs1)
+}
+
+#[aoc(day9, part2)]
+fn part2(input: &[u8]) -> usize {
+    let mut file_id = 0;
+    let mut fs2 = Vec::new();
+    for (i, &b) in input.iter().enumerate() {
+        let v = if i % 2 == 0 {
+            file_id += 1;
+            file_id - 1
+        } else {
+            -1
+        };
+        fs2.push((b as usize, v));
+    }
+    solve(fs2)
+}
+
+/// Day 9: Disk Fragmenter.
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<u8>;
+    type Params = ();
+
+    const YEAR: u32 = 2024;
+    const DAY: u8 = 9;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _: &Self::Params) -> Result<impl Display, SolveError> {
+        Ok(part2(input))
+    }
+}
+
+params! {
+    /// Length of the generated disk map.
+    pub struct Knobs {
+        /// Digits in the disk map, rounded up to odd so it ends with a file.
+        length: usize = 19999,
+    }
+}
+
+/// A disk map of files one to nine blocks long with up to nine free blocks between them.
+impl Generate for Day9 {
+    type Knobs = Knobs;
+
+    fn generate(rng: &mut Rng, knobs: &Knobs) -> String {
+        (0..knobs.length | 1)
+            .map(|i| {
+                let digit = if i % 2 == 0 {
+                    rng.between(1, 9)
+                } else {
+                    rng.between(0, 9)
+                };
+                char::from(b'0' + digit as u8)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str = "2333133121414131402";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 1928);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), 2858);
+    }
+
+    #[test]
+    fn rejects_an_empty_disk_map() {
+        assert!(parse(" \n").is_err());
+    }
+
+    #[test]
+    fn disks_can_be_empty() {
+        let disk = parse("000").unwrap();
+        assert_eq!((part1(&disk), part2(&disk)), (0, 0));
+    }
+}
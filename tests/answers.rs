@@ -0,0 +1,110 @@
+//! Runs every registered solver against the local puzzle inputs and compares the answers with
+//! `answers.toml`. Days without an input file, or whose answers don't name it by its hash, are
+//! skipped with a warning.
+
+use std::path::Path;
+
+use aocrs_2024::{
+    report::{input_hash, Answers},
+    runner,
+};
+use toml::Table;
+
+fn manifest() -> Table {
+    include_str!("../answers.toml")
+        .parse()
+        .expect("answers.toml is not valid TOML")
+}
+
+#[test]
+fn recorded_answers() {
+    let answers = Answers::parse(include_str!("../answers.toml")).unwrap();
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    let manifest = manifest();
+    for solver in runner::solvers() {
+        let recorded = manifest
+            .get(&solver.year.to_string())
+            .and_then(|days| days.get(format!("day{}", solver.day)));
+        if recorded.is_none() {
+            continue;
+        }
+        let Ok(input) =
+            std::fs::read_to_string(root.join(runner::input_path(solver.year, solver.day)))
+        else {
+            eprintln!("{solver}: skipped, no input");
+            continue;
+        };
+        let hash = input_hash(&input);
+        match answers.input_sha256(solver.year, solver.day) {
+            None => {
+                eprintln!(
+                    "{solver}: skipped, answers name no input; \
+                     if they're for this one, record input_sha256 = \"{hash}\""
+                );
+                continue;
+            }
+            Some(recorded) if recorded != hash => {
+                eprintln!("{solver}: skipped, answers are for another input");
+                continue;
+            }
+            Some(_) => {}
+        }
+        let Some(expected) = answers.expected(solver, &hash) else {
+            continue;
+        };
+        checked += 1;
+        match solver.run(&input) {
+            Ok(outcome) if outcome.answer == expected => {}
+            Ok(outcome) => failures.push(format!(
+                "{solver}: expected {expected}, got {}",
+                outcome.answer
+            )),
+            Err(e) => failures.push(format!("{solver}: {e}")),
+        }
+    }
+    eprintln!("checked {checked} recorded answers");
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn manifest_names_registered_solvers() {
+    for (year, days) in manifest() {
+        let days = days.as_table().expect("years are tables");
+        let year: u32 = year
+            .parse()
+            .unwrap_or_else(|_| panic!("`{year}` is not a year"));
+        for (day, answers) in days {
+            let answers = answers.as_table().expect("days are tables");
+            let day: u8 = day
+                .strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .unwrap_or_else(|| panic!("{year}: `{day}` is not a day"));
+            for (key, value) in answers {
+                if key == "input_sha256" {
+                    let hash = value.as_str().unwrap_or_default();
+                    assert!(
+                        hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit()),
+                        "{year} day {day}: `{value}` is not a SHA-256"
+                    );
+                    continue;
+                }
+                let (variant, parts) = match value.as_table() {
+                    Some(parts) => (Some(key.as_str()), parts.keys().collect()),
+                    None => (None, vec![key]),
+                };
+                for part in parts {
+                    let part = part
+                        .strip_prefix("part")
+                        .and_then(|p| p.parse().ok())
+                        .unwrap_or_else(|| panic!("{year} day {day}: `{part}` is not a part"));
+                    assert!(
+                        runner::find(year, day, part, variant).is_some(),
+                        "no solver for {year} day {day} part {part} {variant:?}"
+                    );
+                }
+            }
+        }
+    }
+}